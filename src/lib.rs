@@ -5,6 +5,19 @@ pub(crate) mod utils;
 
 pub use macros::middleware;
 pub use macros::route;
+pub use macros::websocket;
 pub use server::Server;
+pub use structs::body::Body;
+pub use structs::bound_server::BoundServer;
 pub use structs::context::Context;
+pub use structs::cookie::{Cookie, SameSite};
 pub use structs::definition::Returns;
+pub use structs::error::RequestError;
+pub use structs::headers::Headers;
+pub use structs::server_handle::ServerHandle;
+pub use structs::sse::{Event, Sse};
+pub use structs::tls::{ClientAuth, PeerCertificate, TlsConfig, TlsInfo};
+pub use structs::websocket::{Message, WebSocket};
+#[cfg(unix)]
+pub use utils::listen_fds::listen_fds;
+pub use utils::shutdown_signal::shutdown_signal;
@@ -8,7 +8,7 @@
 ///
 /// async fn mid(mut c: Context) -> Returns {
 ///     let start: Instant = Instant::now();
-///     c.response.body = "Middleware Function".to_owned();
+///     c.response.body = "Middleware Function".into();
 ///     (c, None)
 /// }
 ///
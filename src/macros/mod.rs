@@ -1,3 +1,4 @@
 pub mod middleware;
 pub mod route;
 pub mod tail;
+pub mod websocket;
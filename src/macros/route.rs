@@ -6,7 +6,7 @@
 /// use oxidy::{Server, Context, Returns, route};
 ///
 /// async fn route(mut c: Context) -> Returns {
-///     c.response.body = "Get Route Function".to_owned();
+///     c.response.body = "Get Route Function".into();
 ///     (c, None)
 /// }
 ///
@@ -8,12 +8,12 @@
 ///
 /// async fn mid(mut c: Context) -> Returns {
 ///     let start: Instant = Instant::now();
-///     c.response.body = "Middleware Function".to_owned();
+///     c.response.body = "Middleware Function".into();
 ///
 ///     tail!{
 ///         c,
 ///         {
-///             c.response.body = "Tail Function".to_owned();
+///             c.response.body = "Tail Function".into();
 ///             let end: Instant = Instant::now();
 ///             println!("Response Time: {:?}", end.duration_since(start));
 ///             c
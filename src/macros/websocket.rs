@@ -0,0 +1,47 @@
+/// WebSocket Macro
+///
+/// Registers a `GET` route upgrading to a WebSocket. Middlewares run before
+/// the upgrade and can reject it (e.g. with `401`) by not calling the next
+/// function.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Message, WebSocket, websocket};
+///
+/// async fn chat(c: Context, mut ws: WebSocket) {
+///     let room: String = c.request.param("room").await;
+///     let _ = ws.send(Message::Text(format!("Welcome to {}", room))).await;
+///
+///     while let Some(message) = ws.recv().await {
+///         println!("{:?}", message);
+///     }
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(websocket!("/chat/:room", chat));
+/// ```
+#[macro_export]
+macro_rules! websocket {
+    ($path:expr, $func:tt) => {{
+        use std::sync::Arc;
+        use $crate::structs::context::Context;
+        use $crate::structs::definition::{Callback, Upgrade};
+        use $crate::structs::websocket::{upgrade, WebSocket};
+        /*
+         * Runs after the Handshake
+         */
+        let on_upgrade: Arc<Upgrade> = Arc::new(Box::new(move |c: Context, ws: WebSocket| {
+            Box::pin($func(c, ws))
+        }));
+        /*
+         * Function Vec
+         */
+        let mut funcs: Vec<Arc<Callback>> = Vec::new();
+        funcs.push(Arc::new(Box::new(move |c: Context| {
+            Box::pin(upgrade(c, on_upgrade.clone()))
+        })));
+
+        ("get", $path, funcs)
+    }};
+}
@@ -1,14 +1,24 @@
+use crate::structs::bound_server::BoundServer;
+use crate::structs::config::Config;
 use crate::structs::definition::Callback;
-use crate::utils::handler::handler;
-use std::net::SocketAddr;
+use crate::structs::stats::Stats;
+use crate::structs::tls::TlsConfig;
+use futures::future::pending;
+use std::future::Future;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+#[cfg(unix)]
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::io::Error;
-use tokio::net::{TcpListener, TcpStream};
-use tokio::spawn;
+use std::time::Duration;
+use tokio::io::{Error, ErrorKind};
 
 #[derive(Default, Clone)]
 pub struct Server {
     pub(crate) adds: Vec<(String, String, Vec<Arc<Callback>>)>,
+    pub(crate) config: Config,
+    pub(crate) stats: Arc<Stats>,
 }
 
 impl Server {
@@ -38,7 +48,7 @@ impl Server {
     /// use oxidy::{Server, Context, Returns, route};
     ///
     /// async fn route(mut c: Context) -> Returns {
-    ///     c.response.body = "Get Route Function".to_owned();
+    ///     c.response.body = "Get Route Function".into();
     ///     (c, None)
     /// }
     ///
@@ -49,7 +59,10 @@ impl Server {
         self.adds
             .push((args.0.to_owned(), args.1.to_owned(), args.2));
     }
-    /// Run / Listen
+    /// Set Maximum Request Body Size (Bytes)
+    ///
+    /// Requests with a larger body are answered with `413 Payload Too Large`.
+    /// Default is 1 MiB.
     ///
     /// # Example
     ///
@@ -57,28 +70,397 @@ impl Server {
     /// use oxidy::Server;
     ///
     /// let mut app = Server::new();
-    /// /* app.run("127.0.0.1:3000").await; */
+    /// app.set_max_body_size(8 * 1024 * 1024);
     /// ```
-    pub async fn run(&self, address: &str) {
-        /*
-         * Bind Listener
-         */
-        let listener: TcpListener = TcpListener::bind(address)
-            .await
-            .expect("[Error] Fail to bind TCP Listener");
-        /*
-         * Connection Loop
-         */
-        loop {
-            let listener_accept: Result<(TcpStream, SocketAddr), Error> = listener.accept().await;
+    pub fn set_max_body_size(&mut self, size: usize) {
+        self.config.max_body_size = size;
+    }
+    /// Set Maximum Request Header Size (Bytes)
+    ///
+    /// Includes the request line. Larger headers are answered with
+    /// `431 Request Header Fields Too Large`. Default is 16 KiB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_header_size(32 * 1024);
+    /// ```
+    pub fn set_max_header_size(&mut self, size: usize) {
+        self.config.max_header_size = size;
+    }
+    /// Set Maximum Request Header Count
+    ///
+    /// Requests with more header fields are answered with
+    /// `431 Request Header Fields Too Large`. Default is 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_header_count(50);
+    /// ```
+    pub fn set_max_header_count(&mut self, count: usize) {
+        self.config.max_header_count = count;
+    }
+    /// Set Upload Directory
+    ///
+    /// Directory for the temporary files of `multipart/form-data` file
+    /// parts. Default is the system temporary directory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_upload_dir("/var/tmp/uploads");
+    /// ```
+    pub fn set_upload_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.config.upload_dir = dir.into();
+    }
+    /// Set Maximum Upload File Size (Bytes)
+    ///
+    /// Per file part of a `multipart/form-data` body. Larger files are
+    /// answered with `413 Payload Too Large`. Default is 10 MiB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_file_size(100 * 1024 * 1024);
+    /// ```
+    pub fn set_max_file_size(&mut self, size: usize) {
+        self.config.max_file_size = size;
+    }
+    /// Set Maximum Upload Size (Bytes)
+    ///
+    /// Whole `multipart/form-data` body, all parts together. Larger uploads
+    /// are answered with `413 Payload Too Large`. Default is 50 MiB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_upload_size(500 * 1024 * 1024);
+    /// ```
+    pub fn set_max_upload_size(&mut self, size: usize) {
+        self.config.max_upload_size = size;
+    }
+    /// Set Maximum Number of Upload Parts
+    ///
+    /// Fields & files of a `multipart/form-data` body together. More parts
+    /// are answered with `413 Payload Too Large`. Default is 1000.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_parts(50);
+    /// ```
+    pub fn set_max_parts(&mut self, count: usize) {
+        self.config.max_parts = count;
+    }
+    /// Set Idle Timeout of Persistent Connections
+    ///
+    /// Connections waiting longer for the next request are closed, an
+    /// incomplete request header is answered with `408 Request Timeout`.
+    /// Default is 5 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_idle_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.config.idle_timeout = timeout;
+    }
+    /// Set Request Body Timeout
+    ///
+    /// Time a client gets to send the whole request body. Slower requests
+    /// are answered with `408 Request Timeout`. Default is 60 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_body_timeout(Duration::from_secs(300));
+    /// ```
+    pub fn set_body_timeout(&mut self, timeout: Duration) {
+        self.config.body_timeout = timeout;
+    }
+    /// Set Maximum Requests per Connection
+    ///
+    /// The response to the last request carries `Connection: close`.
+    /// Default is 1000.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_max_requests(100);
+    /// ```
+    pub fn set_max_requests(&mut self, count: usize) {
+        self.config.max_requests = count.max(1);
+    }
+    /// Set Shutdown Timeout
+    ///
+    /// After the shutdown signal, running requests get this long to finish
+    /// before their connections are closed. Default is 30 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_shutdown_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn set_shutdown_timeout(&mut self, timeout: Duration) {
+        self.config.shutdown_timeout = timeout;
+    }
+    /// Set Permission Mode of Unix Socket Files
+    ///
+    /// Applied right after binding (e.g. `0o660` to let a proxy in the same
+    /// group connect). Default keeps the mode given by the umask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_unix_mode(0o660);
+    /// ```
+    pub fn set_unix_mode(&mut self, mode: u32) {
+        self.config.unix_mode = Some(mode);
+    }
+    /// Bind Listener
+    ///
+    /// Fails instead of panicking (e.g. address in use). Bind to port `0`
+    /// to let the system choose a free port.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// async fn start() {
+    ///     let app = Server::new();
+    ///
+    ///     match app.bind("127.0.0.1:0").await {
+    ///         Ok(bound) => println!("Listening on {:?}", bound.local_addr()),
+    ///         Err(e) => println!("Fail to bind: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn bind(&self, address: &str) -> Result<BoundServer, Error> {
+        let mut bound: BoundServer = BoundServer::new(self.to_owned());
+        bound.listen(address).await?;
 
-            if listener_accept.is_err() {
-                continue;
-            }
+        Ok(bound)
+    }
+    /// Bind Listener with TLS
+    ///
+    /// Fails on a bind error or when certificate / key cannot be loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, TlsConfig};
+    ///
+    /// async fn start() {
+    ///     let app = Server::new();
+    ///     let tls = TlsConfig::new("cert.pem", "key.pem");
+    ///
+    ///     if let Err(e) = app.bind_tls("127.0.0.1:3443", tls).await {
+    ///         println!("Fail to start TLS: {}", e);
+    ///     }
+    /// }
+    /// ```
+    pub async fn bind_tls(&self, address: &str, tls: TlsConfig) -> Result<BoundServer, Error> {
+        let mut bound: BoundServer = BoundServer::new(self.to_owned());
+        bound.listen_tls(address, tls).await?;
+
+        Ok(bound)
+    }
+    /// Bind Unix Socket Listener
+    ///
+    /// A stale socket file of a previous run is replaced, the file is
+    /// removed again when the server stops. `Request::address` is
+    /// `unix:<path>` with the peer process & user id when available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// async fn start() {
+    ///     let app = Server::new();
+    ///
+    ///     if let Err(e) = app.bind_unix("/run/oxidy/app.sock").await {
+    ///         println!("Fail to bind: {}", e);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub async fn bind_unix(&self, path: impl AsRef<Path>) -> Result<BoundServer, Error> {
+        let mut bound: BoundServer = BoundServer::new(self.to_owned());
+        bound.listen_unix(path).await?;
 
-            let (stream, address) = listener_accept.expect("[Error] Fail to Accept Connection");
+        Ok(bound)
+    }
+    /// Bind inherited Listener (already bound & listening TCP or Unix socket)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::os::fd::{FromRawFd, OwnedFd};
+    /// use oxidy::Server;
+    ///
+    /// fn start() -> std::io::Result<()> {
+    ///     let app = Server::new();
+    ///
+    ///     /* Descriptor 3 passed by the parent process */
+    ///     let fd: OwnedFd = unsafe { OwnedFd::from_raw_fd(3) };
+    ///     /* let bound = app.bind_fd(fd)?; */
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub fn bind_fd(&self, fd: OwnedFd) -> Result<BoundServer, Error> {
+        let mut bound: BoundServer = BoundServer::new(self.to_owned());
+        bound.listen_fd(fd)?;
+
+        Ok(bound)
+    }
+    /// Bind Listeners passed by systemd Socket Activation
+    ///
+    /// Fails when the process was not socket activated. The port stays open
+    /// while the service restarts, connections wait in the backlog.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, shutdown_signal};
+    ///
+    /// async fn start() {
+    ///     let app = Server::new();
+    ///
+    ///     let bound = match app.bind_systemd() {
+    ///         Ok(x) => x,
+    ///         Err(_) => app.bind("127.0.0.1:3000").await.unwrap(),
+    ///     };
+    ///
+    ///     /* bound.run_with_shutdown(shutdown_signal()).await; */
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub fn bind_systemd(&self) -> Result<BoundServer, Error> {
+        let mut bound: BoundServer = BoundServer::new(self.to_owned());
 
-            spawn(handler(self.to_owned(), address, stream));
+        if bound.listen_systemd()? == 0 {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "No Sockets passed by systemd (LISTEN_FDS)",
+            ));
         }
+
+        Ok(bound)
+    }
+    /// Run / Listen
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// /* app.run("127.0.0.1:3000").await; */
+    /// ```
+    pub async fn run(&self, address: &str) {
+        self.run_with_shutdown(address, pending()).await;
+    }
+    /// Run / Listen until the Signal completes
+    ///
+    /// Then no more connections are accepted, idle connections are closed
+    /// and running requests finish (up to the shutdown timeout) before it
+    /// returns. `shutdown_signal()` completes on SIGTERM or SIGINT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, shutdown_signal};
+    ///
+    /// let mut app = Server::new();
+    /// /* app.run_with_shutdown("127.0.0.1:3000", shutdown_signal()).await; */
+    /// ```
+    pub async fn run_with_shutdown(&self, address: &str, signal: impl Future<Output = ()>) {
+        self.bind(address)
+            .await
+            .expect("[Error] Fail to bind TCP Listener")
+            .run_with_shutdown(signal)
+            .await;
+    }
+    /// Run / Listen with TLS
+    ///
+    /// Certificate chain and private key are loaded from PEM files. HTTP/2
+    /// (`h2`) and HTTP/1.1 are offered by ALPN. See `TlsConfig::reload` for
+    /// certificate rotation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, TlsConfig};
+    ///
+    /// let mut app = Server::new();
+    /// let tls = TlsConfig::new("cert.pem", "key.pem");
+    /// /* app.run_tls("127.0.0.1:3443", tls).await; */
+    /// ```
+    pub async fn run_tls(&self, address: &str, tls: TlsConfig) {
+        self.bind_tls(address, tls)
+            .await
+            .expect("[Error] Fail to bind TLS Listener")
+            .run()
+            .await;
+    }
+    /// Run / Listen on a Unix Socket
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::Server;
+    ///
+    /// let mut app = Server::new();
+    /// app.set_unix_mode(0o660);
+    /// /* app.run_unix("/run/oxidy/app.sock").await; */
+    /// ```
+    #[cfg(unix)]
+    pub async fn run_unix(&self, path: impl AsRef<Path>) {
+        self.bind_unix(path)
+            .await
+            .expect("[Error] Fail to bind Unix Listener")
+            .run()
+            .await;
     }
 }
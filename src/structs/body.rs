@@ -0,0 +1,157 @@
+use futures::stream::BoxStream;
+use futures::Stream;
+use std::fmt::{Debug, Formatter, Result};
+use std::io::Error;
+use std::sync::{Arc, Mutex};
+
+/*
+ * Stream of Body Chunks, taken once when the Response is written
+ */
+pub(crate) type ChunkStream = BoxStream<'static, std::result::Result<Vec<u8>, Error>>;
+
+/// Response Body
+///
+/// Raw bytes written unmodified, so binary payloads (images, PDFs,
+/// compressed data) are sent as-is. Converts from `String`, `&str`,
+/// `Vec<u8>` & `&[u8]`, or streams chunks with `Body::stream`.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, route};
+///
+/// async fn route(mut c: Context) -> Returns {
+///     let png: Vec<u8> = vec![0x89, b'P', b'N', b'G'];
+///     c.response.body = png.into();
+///     c.response.content_type = "image/png".to_owned();
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("get /logo.png", route));
+/// ```
+#[derive(Clone, Default)]
+pub struct Body {
+    data: Vec<u8>,
+    stream: Option<Arc<Mutex<Option<ChunkStream>>>>,
+}
+
+impl Body {
+    /// New empty Body
+    pub fn new() -> Body {
+        Default::default()
+    }
+    /// Streamed Body
+    ///
+    /// Chunks are written as they are produced, with `Transfer-Encoding:
+    /// chunked` on HTTP/1.1 or until the connection closes on HTTP/1.0.
+    /// Writing waits for the socket, so a slow client slows the stream down.
+    /// An `Err` chunk aborts the connection, leaving the response truncated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, Body, route};
+    /// use futures::{stream, StreamExt};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let rows = stream::iter(0..1_000_000).map(|i: u32| {
+    ///         Ok::<Vec<u8>, std::io::Error>(format!("{},row {}\n", i, i).into_bytes())
+    ///     });
+    ///
+    ///     c.response.body = Body::stream(rows);
+    ///     c.response.content_type = "text/csv".to_owned();
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /export.csv", route));
+    /// ```
+    pub fn stream<S>(stream: S) -> Body
+    where
+        S: Stream<Item = std::result::Result<Vec<u8>, Error>> + Send + 'static,
+    {
+        Body {
+            data: Vec::new(),
+            stream: Some(Arc::new(Mutex::new(Some(Box::pin(stream))))),
+        }
+    }
+    /// Check if the Body is streamed
+    pub fn is_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+    /// Get Body Bytes
+    ///
+    /// Empty for streamed Bodies.
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+    /// Get Body as UTF-8 Text
+    pub fn text(&self) -> Option<String> {
+        String::from_utf8(self.data.clone()).ok()
+    }
+    /// Length in Bytes (0 for streamed Bodies)
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// Check if the Body is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl Body {
+    /*
+     * Take the Stream out (only the first Response write gets it)
+     */
+    pub(crate) fn take_stream(&self) -> Option<ChunkStream> {
+        self.stream
+            .as_ref()
+            .and_then(|x: &Arc<Mutex<Option<ChunkStream>>>| x.lock().ok()?.take())
+    }
+}
+
+impl Debug for Body {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.stream {
+            Some(_) => f.write_str("Body(Stream)"),
+            None => f.debug_struct("Body").field("data", &self.data).finish(),
+        }
+    }
+}
+
+impl From<String> for Body {
+    fn from(value: String) -> Self {
+        Body {
+            data: value.into_bytes(),
+            stream: None,
+        }
+    }
+}
+
+impl From<&str> for Body {
+    fn from(value: &str) -> Self {
+        Body {
+            data: value.as_bytes().to_vec(),
+            stream: None,
+        }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(value: Vec<u8>) -> Self {
+        Body {
+            data: value,
+            stream: None,
+        }
+    }
+}
+
+impl From<&[u8]> for Body {
+    fn from(value: &[u8]) -> Self {
+        Body {
+            data: value.to_vec(),
+            stream: None,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use std::sync::{Arc, RwLock};
+
+/*
+ * Current Certificate of a TLS Listener, swapped as a whole on Reload
+ * Handshakes already running keep the Certificate they started with
+ */
+#[derive(Debug, Default)]
+pub(crate) struct CertResolver {
+    key: RwLock<Option<Arc<CertifiedKey>>>,
+}
+
+impl CertResolver {
+    pub(crate) fn set(&self, key: CertifiedKey) {
+        let mut current = self.key.write().unwrap_or_else(|e| e.into_inner());
+        *current = Some(Arc::new(key));
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, _: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        self.key.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
@@ -0,0 +1,41 @@
+use std::env::temp_dir;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/*
+ * Server Limits & Settings
+ */
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+    pub(crate) max_body_size: usize,
+    pub(crate) max_header_size: usize,
+    pub(crate) max_header_count: usize,
+    pub(crate) upload_dir: PathBuf,
+    pub(crate) max_file_size: usize,
+    pub(crate) max_upload_size: usize,
+    pub(crate) max_parts: usize,
+    pub(crate) idle_timeout: Duration,
+    pub(crate) body_timeout: Duration,
+    pub(crate) max_requests: usize,
+    pub(crate) shutdown_timeout: Duration,
+    pub(crate) unix_mode: Option<u32>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            max_body_size: 1024 * 1024,
+            max_header_size: 16 * 1024,
+            max_header_count: 100,
+            upload_dir: temp_dir(),
+            max_file_size: 10 * 1024 * 1024,
+            max_upload_size: 50 * 1024 * 1024,
+            max_parts: 1000,
+            idle_timeout: Duration::from_secs(5),
+            body_timeout: Duration::from_secs(60),
+            max_requests: 1000,
+            shutdown_timeout: Duration::from_secs(30),
+            unix_mode: None,
+        }
+    }
+}
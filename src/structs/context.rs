@@ -16,7 +16,7 @@ pub struct Context {
     ///
     /// async fn mid(mut c: Context) -> Returns {
     ///     let start: Instant = Instant::now();
-    ///     c.response.body = "Middleware Function".to_owned();
+    ///     c.response.body = "Middleware Function".into();
     ///     c.next = true;
     ///     (c, None)
     /// }
@@ -48,9 +48,9 @@ impl Context {
     /// async fn route(mut c: Context) -> Returns {
     ///     let user: Option<String> = c.get_state("user").await;
     ///     match user {
-    ///         Some(u) => c.response.body = format!("Username: {}", u),
+    ///         Some(u) => c.response.body = format!("Username: {}", u).into(),
     ///         None => {
-    ///             c.response.body = format!("Username not found");
+    ///             c.response.body = format!("Username not found").into();
     ///             c.response.status = 404;
     ///         },
     ///     }
@@ -81,9 +81,9 @@ impl Context {
     /// async fn route(mut c: Context) -> Returns {
     ///     let user: Option<String> = c.get_state("user").await;
     ///     match user {
-    ///         Some(u) => c.response.body = format!("Username: {}", u),
+    ///         Some(u) => c.response.body = format!("Username: {}", u).into(),
     ///         None => {
-    ///             c.response.body = format!("Username not found");
+    ///             c.response.body = format!("Username not found").into();
     ///             c.response.status = 404;
     ///         },
     ///     }
@@ -114,9 +114,9 @@ impl Context {
     /// async fn route(mut c: Context) -> Returns {
     ///     let user: Option<String> = c.get_state("user").await;
     ///     match user {
-    ///         Some(u) => c.response.body = format!("Username: {}", u),
+    ///         Some(u) => c.response.body = format!("Username: {}", u).into(),
     ///         None => {
-    ///             c.response.body = format!("Username not found");
+    ///             c.response.body = format!("Username not found").into();
     ///             c.response.status = 404;
     ///         },
     ///     }
@@ -0,0 +1,181 @@
+use crate::utils::http_date::http_date;
+use std::fmt::{Display, Formatter, Result};
+use std::io::{Error, ErrorKind};
+use std::time::SystemTime;
+
+/// Cookie SameSite Attribute
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+/// Response Cookie (Set-Cookie)
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use oxidy::{Server, Context, Returns, Cookie, SameSite, route};
+///
+/// async fn route(mut c: Context) -> Returns {
+///     let mut cookie: Cookie = Cookie::new("session", "abc123");
+///     cookie.path = Some("/".to_owned());
+///     cookie.max_age = Some(3600);
+///     cookie.expires = Some(SystemTime::now() + Duration::from_secs(3600));
+///     cookie.secure = true;
+///     cookie.http_only = true;
+///     cookie.same_site = Some(SameSite::Lax);
+///
+///     if let Err(e) = c.response.set_cookie(cookie).await {
+///         println!("Invalid Cookie: {}", e);
+///     }
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("post /login", route));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub path: Option<String>,
+    pub domain: Option<String>,
+    /// Lifetime in Seconds
+    pub max_age: Option<u64>,
+    pub expires: Option<SystemTime>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    /// New Cookie without Attributes (Session Cookie)
+    pub fn new(name: &str, value: &str) -> Cookie {
+        Cookie {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            path: None,
+            domain: None,
+            max_age: None,
+            expires: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+    /*
+     * Name is a Token, Value Cookie-Octets (optionally quoted), Path & Domain
+     * without ';' or Control Characters (RFC 6265 Section 4.1.1), so nothing
+     * can add Attributes or break the Header
+     */
+    pub(crate) fn check(&self) -> std::result::Result<(), Error> {
+        let is_token = |b: u8| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b);
+
+        if self.name.is_empty() || !self.name.bytes().all(is_token) {
+            return Err(invalid(format!("Invalid Cookie Name: {:?}", self.name)));
+        }
+
+        let value: &str = self
+            .value
+            .strip_prefix('"')
+            .and_then(|x: &str| x.strip_suffix('"'))
+            .unwrap_or(&self.value);
+
+        if !value.bytes().all(is_cookie_octet) {
+            return Err(invalid(format!("Invalid Cookie Value: {:?}", self.value)));
+        }
+
+        for x in [&self.path, &self.domain].into_iter().flatten() {
+            if x.chars().any(|c: char| c == ';' || c.is_control()) {
+                return Err(invalid(format!("Invalid Cookie Attribute: {:?}", x)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * %x21 / %x23-2B / %x2D-3A / %x3C-5B / %x5D-7E
+ * (no Whitespace, DQUOTE, Comma, Semicolon or Backslash)
+ */
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+/*
+ * Set-Cookie Header Value
+ */
+impl Display for Cookie {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}={}", self.name, self.value)?;
+
+        if let Some(x) = &self.path {
+            write!(f, "; Path={}", x)?;
+        }
+
+        if let Some(x) = &self.domain {
+            write!(f, "; Domain={}", x)?;
+        }
+
+        if let Some(x) = self.max_age {
+            write!(f, "; Max-Age={}", x)?;
+        }
+
+        if let Some(x) = self.expires {
+            write!(f, "; Expires={}", http_date(x))?;
+        }
+
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+
+        match self.same_site {
+            Some(SameSite::Strict) => f.write_str("; SameSite=Strict"),
+            Some(SameSite::Lax) => f.write_str("; SameSite=Lax"),
+            Some(SameSite::None) => f.write_str("; SameSite=None"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let mut cookie: Cookie = Cookie::new("session", "abc123");
+        cookie.path = Some("/".to_owned());
+        cookie.domain = Some("example.com".to_owned());
+
+        assert!(cookie.check().is_ok());
+        assert!(Cookie::new("quoted", "\"a=b\"").check().is_ok());
+        assert!(Cookie::new("empty", "").check().is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Cookie::new("s", "x; Domain=evil.com").check().is_err());
+        assert!(Cookie::new("s", "a\r\nX-Injected: 1").check().is_err());
+        assert!(Cookie::new("s", "a b").check().is_err());
+        assert!(Cookie::new("s=1", "x").check().is_err());
+        assert!(Cookie::new("", "x").check().is_err());
+
+        let mut cookie: Cookie = Cookie::new("s", "x");
+        cookie.path = Some("/; Secure".to_owned());
+
+        assert!(cookie.check().is_err());
+    }
+}
@@ -1,8 +1,49 @@
 use crate::structs::context::Context;
+use crate::structs::websocket::WebSocket;
 use futures::future::BoxFuture;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
 
 pub type Callback = Box<dyn Fn(Context) -> BoxFuture<'static, Returns> + Send + Sync>;
 
 pub type Returns = (Context, Option<Tail>);
 
 pub(crate) type Tail = Box<dyn Fn(Context) -> BoxFuture<'static, Context> + Send + Sync>;
+
+pub type Upgrade = Box<dyn Fn(Context, WebSocket) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/*
+ * WebSocket Handler waiting for the 101 Response to be sent
+ */
+#[derive(Clone)]
+pub(crate) struct OnUpgrade(pub(crate) Arc<Upgrade>);
+
+impl Debug for OnUpgrade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str("OnUpgrade")
+    }
+}
+
+/*
+ * Why an incoming Request could not be read
+ */
+pub(crate) enum ReadError {
+    /* Connection closed or broken, nothing to answer */
+    Close,
+    /* Answer with this Status & close */
+    Status(usize),
+}
+
+/*
+ * Connection Stream (TCP or TLS)
+ */
+pub(crate) trait Io: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Io for T {}
+
+pub(crate) type IoStream = Box<dyn Io>;
+
+pub(crate) type IoReader = ReadHalf<IoStream>;
+
+pub(crate) type IoWriter = WriteHalf<IoStream>;
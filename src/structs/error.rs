@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// Request Extraction Error
+///
+/// Returned when the request can not be turned into the asked type.
+/// Pass it to `Response::error` to answer with its status & a JSON body.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, RequestError, route};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// async fn route(mut c: Context) -> Returns {
+///     let user: Result<User, RequestError> = c.request.json().await;
+///     match user {
+///         Ok(u) => c.response.body = format!("Username: {}", u.name).into(),
+///         Err(e) => c.response.error(e).await,
+///     }
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("post /user", route));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RequestError {
+    /// HTTP Status to answer with
+    pub status: usize,
+    /// Human readable reason
+    pub message: String,
+}
+
+impl RequestError {
+    pub(crate) fn new(status: usize, message: &str) -> RequestError {
+        RequestError {
+            status,
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} ({})", self.message, self.status)
+    }
+}
+
+impl std::error::Error for RequestError {}
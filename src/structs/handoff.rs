@@ -0,0 +1,13 @@
+use std::process::{Child, Command};
+use tokio::io::Error;
+use tokio::sync::oneshot::Sender;
+
+/*
+ * Request to start a new Process with the Listeners of a running Server
+ * Answered with the started Process, or why it could not be started
+ */
+#[derive(Debug)]
+pub(crate) struct Handoff {
+    pub(crate) command: Command,
+    pub(crate) reply: Sender<Result<Child, Error>>,
+}
@@ -0,0 +1,166 @@
+use std::slice::Iter;
+
+/// Header Fields
+///
+/// Case-insensitive multimap keeping the order & the original case of the
+/// field names. Shared by `Request`, `Response` & multipart `Part`.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, route};
+///
+/// async fn route(mut c: Context) -> Returns {
+///     let accept: Vec<&str> = c.request.headers.get_all("accept");
+///     println!("Accept: {:?}", accept);
+///
+///     for (k, v) in &c.request.headers {
+///         println!("{}: {}", k, v);
+///     }
+///
+///     c.response.headers.append("Vary", "Accept");
+///     c.response.headers.append("Vary", "Accept-Encoding");
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("get /", route));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Headers {
+    entries: Vec<(String, String)>,
+}
+
+impl Headers {
+    /// New empty Header Fields
+    pub fn new() -> Headers {
+        Default::default()
+    }
+    /// Get first Value of a Field
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+    /// Get all Values of a repeated Field, in order
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+    /// Check if a Field is present
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case(key))
+    }
+    /// Check if a comma separated Field contains a Token (case-insensitive)
+    ///
+    /// `Connection: keep-alive, Upgrade` contains the Token `upgrade`.
+    pub fn has_token(&self, key: &str, token: &str) -> bool {
+        self.get_all(key)
+            .iter()
+            .flat_map(|v: &&str| v.split(','))
+            .any(|v: &str| v.trim().eq_ignore_ascii_case(token))
+    }
+    /// Set a Field, replacing every existing Value
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.remove(key);
+        self.append(key, value);
+    }
+    /// Add a Value, keeping existing Values of the Field
+    pub fn append(&mut self, key: &str, value: &str) {
+        self.entries.push((key.to_owned(), value.to_owned()));
+    }
+    /// Remove every Value of a Field
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+    }
+    /// Iterate over all Fields as (Name, Value)
+    pub fn iter(&self) -> Iter<'_, (String, String)> {
+        self.entries.iter()
+    }
+    /// Number of Field Values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Check if there is no Field
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a Headers {
+    type Item = &'a (String, String);
+    type IntoIter = Iter<'a, (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_insensitive() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Content-Type", "text/plain");
+
+        assert_eq!(headers.get("content-type"), Some("text/plain"));
+        assert!(headers.contains("CONTENT-TYPE"));
+        assert!(!headers.contains("content-length"));
+        /*
+         * Original case is kept
+         */
+        assert_eq!(headers.iter().next().unwrap().0, "Content-Type");
+    }
+
+    #[test]
+    fn append_get_all() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Vary", "Accept");
+        headers.append("Host", "a");
+        headers.append("vary", "Accept-Encoding");
+
+        assert_eq!(headers.get("vary"), Some("Accept"));
+        assert_eq!(headers.get_all("VARY"), vec!["Accept", "Accept-Encoding"]);
+        assert!(headers.get_all("link").is_empty());
+        assert_eq!(headers.len(), 3);
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Set-Cookie", "a=1");
+        headers.append("Host", "a");
+        headers.append("set-cookie", "b=2");
+
+        headers.insert("SET-COOKIE", "c=3");
+        assert_eq!(headers.get_all("set-cookie"), vec!["c=3"]);
+
+        headers.remove("Set-Cookie");
+        assert!(!headers.contains("set-cookie"));
+        assert_eq!(headers.len(), 1);
+
+        headers.remove("host");
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn has_token() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Connection", "keep-alive, Upgrade");
+        headers.append("Connection", "HTTP2-Settings");
+
+        assert!(headers.has_token("connection", "upgrade"));
+        assert!(headers.has_token("connection", "http2-settings"));
+        assert!(!headers.has_token("connection", "close"));
+        assert!(!headers.has_token("connection", "keep"));
+        assert!(!headers.has_token("upgrade", "h2c"));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::structs::definition::IoStream;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::os::fd::{AsFd, BorrowedFd};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::Error;
+use tokio::net::TcpListener;
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+/*
+ * Listening Socket of a Server
+ */
+#[derive(Debug)]
+pub(crate) enum Listener {
+    Tcp(TcpListener),
+    /*
+     * Socket File is removed when the Listener is dropped, unless the
+     * Socket was inherited or handed over to another Process
+     */
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: PathBuf,
+        cleanup: AtomicBool,
+    },
+}
+
+impl Listener {
+    /*
+     * Next Connection & a Description of its Peer
+     */
+    pub(crate) async fn accept(&self) -> Result<(IoStream, String), Error> {
+        match self {
+            Listener::Tcp(listener) => {
+                let (stream, address) = listener.accept().await?;
+
+                Ok((Box::new(stream), address.to_string()))
+            }
+            #[cfg(unix)]
+            Listener::Unix { listener, path, .. } => {
+                let (stream, _) = listener.accept().await?;
+
+                let peer: String = unix_peer(&stream, path);
+
+                Ok((Box::new(stream), peer))
+            }
+        }
+    }
+    /*
+     * Local Address, Unix Sockets have none
+     */
+    pub(crate) fn local_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix { .. } => None,
+        }
+    }
+    /*
+     * Leave the Socket File to the Process the Socket was handed over to
+     */
+    pub(crate) fn keep_file(&self) {
+        #[cfg(unix)]
+        if let Listener::Unix { cleanup, .. } = self {
+            cleanup.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(unix)]
+impl AsFd for Listener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match self {
+            Listener::Tcp(listener) => listener.as_fd(),
+            Listener::Unix { listener, .. } => listener.as_fd(),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Listener::Unix { path, cleanup, .. } = self {
+            if *cleanup.get_mut() {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/*
+ * Clients of a Unix Socket are usually unnamed, their Credentials say more
+ * e.g. "unix:/run/app.sock (pid 1234, uid 33)"
+ */
+#[cfg(unix)]
+fn unix_peer(stream: &UnixStream, path: &Path) -> String {
+    let peer: String = format!("unix:{}", path.display());
+
+    match stream.peer_cred() {
+        Ok(cred) => match cred.pid() {
+            Some(pid) => format!("{} (pid {}, uid {})", peer, pid, cred.uid()),
+            None => format!("{} (uid {})", peer, cred.uid()),
+        },
+        Err(_) => peer,
+    }
+}
@@ -1,4 +1,20 @@
+pub mod body;
+pub mod bound_server;
+pub(crate) mod cert_resolver;
+pub(crate) mod config;
 pub mod context;
+pub mod cookie;
 pub mod definition;
+pub mod error;
+pub(crate) mod handoff;
+pub mod headers;
+pub(crate) mod listener;
+pub mod part;
 pub mod request;
 pub mod response;
+pub mod server_handle;
+pub(crate) mod shutdown;
+pub mod sse;
+pub(crate) mod stats;
+pub mod tls;
+pub mod websocket;
@@ -0,0 +1,127 @@
+use crate::structs::headers::Headers;
+use std::path::{Path, PathBuf};
+use tokio::fs::{copy, remove_file, rename};
+use tokio::io::Error;
+
+#[derive(Clone, Debug)]
+pub struct Part {
+    /// Field Name from `Content-Disposition`
+    pub name: String,
+    /// Client File Name (File Parts only)
+    ///
+    /// Sent by the client, never use it as a path without sanitizing.
+    pub filename: Option<String>,
+    /// Part Content Type
+    pub content_type: Option<String>,
+    /// Part Headers
+    pub headers: Headers,
+    /// Temporary File holding the upload (File Parts only)
+    ///
+    /// Removed after the response is sent, use `persist` to keep it.
+    pub path: Option<PathBuf>,
+    /// Size in Bytes
+    pub size: usize,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Part {
+    /// Get Part Header
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     for part in c.request.parts().await {
+    ///         let id: Option<String> = part.header("content-id").await;
+    ///         println!("{}: {:?}", part.name, id);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn header(&self, key: &str) -> Option<String> {
+        self.headers.get(key).map(|v: &str| v.to_owned())
+    }
+    /// Get Field Value (Bytes)
+    ///
+    /// Empty for File Parts, their content is at `path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     if let Some(p) = c.request.part("signature").await {
+    ///         println!("Signature: {:?}", p.bytes().await);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+    /// Get Field Value (UTF-8 Text)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     if let Some(p) = c.request.part("title").await {
+    ///         println!("Title: {:?}", p.text().await);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn text(&self) -> Option<String> {
+        String::from_utf8(self.data.clone()).ok()
+    }
+    /// Keep Uploaded File
+    ///
+    /// Moves the temporary file to `destination`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     if let Some(p) = c.request.part("avatar").await {
+    ///         if p.persist("/var/data/avatar.png").await.is_err() {
+    ///             c.response.status = 500;
+    ///         }
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn persist(&self, destination: impl AsRef<Path>) -> Result<(), Error> {
+        let path: &PathBuf = match &self.path {
+            Some(x) => x,
+            None => return tokio::fs::write(destination, &self.data).await,
+        };
+        /*
+         * Rename fails across file systems, fall back to copy
+         */
+        if rename(path, &destination).await.is_err() {
+            copy(path, &destination).await?;
+            let _ = remove_file(path).await;
+        }
+
+        Ok(())
+    }
+}
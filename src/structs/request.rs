@@ -1,9 +1,20 @@
+use crate::structs::error::RequestError;
+use crate::structs::headers::Headers;
+use crate::structs::part::Part;
+use crate::structs::tls::TlsInfo;
+use crate::utils::deserialize_pairs::{deserialize_pairs, PairsError};
 use crate::utils::get_vec::get_vec;
+use crate::utils::parse_mime::parse_mime;
+use crate::utils::parse_urlencoded::parse_urlencoded;
+use serde::de::DeserializeOwned;
 
 #[derive(Clone, Debug)]
 pub struct Request {
     /// Get Client Address/IP with Port
     ///
+    /// On a Unix socket: `unix:<path>` with the peer process & user id,
+    /// e.g. `unix:/run/app.sock (pid 1234, uid 33)`.
+    ///
     /// # Example
     ///
     /// ```
@@ -34,22 +45,62 @@ pub struct Request {
     /// app.add(route!("get /", route));
     /// ```
     pub header: String,
+    /// Get Request Header Fields
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     let cookies: Vec<&str> = c.request.headers.get_all("cookie");
+    ///     println!("Cookie Headers: {}", cookies.len());
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub headers: Headers,
+    /// Get TLS Session (ALPN Protocol & SNI), `None` without TLS
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     let secure: bool = c.request.tls.is_some();
+    ///     println!("Secure: {}", secure);
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub tls: Option<TlsInfo>,
     /*
      * Store / Cache
      */
-    pub(crate) header_store: Vec<(String, String)>,
     pub(crate) param_store: Vec<(String, String)>,
     pub(crate) query_store: Vec<(String, String)>,
+    pub(crate) form_store: Vec<(String, String)>,
     pub method: String,
     pub url: String,
     pub path: String,
     pub query: String,
     pub http_version: f64,
+    pub(crate) body: Vec<u8>,
+    pub(crate) trailers: Headers,
+    pub(crate) parts: Vec<Part>,
 }
 
 impl Request {
     /// Get Request Header
     ///
+    /// Values of a repeated field are combined with `", "` (`"; "` for
+    /// `Cookie`).
+    ///
     /// # Example
     ///
     /// ```
@@ -67,56 +118,117 @@ impl Request {
     /// let mut app = Server::new();
     /// app.add(route!("get /", route));
     /// ```
-    pub async fn header(&mut self, key: &str) -> Option<String> {
-        if !self.header_store.is_empty() {
-            return get_vec(&self.header_store, key.to_owned()).await;
-        }
-
-        let mut found_value: Option<String> = None;
+    pub async fn header(&self, key: &str) -> Option<String> {
+        let values: Vec<&str> = self.headers.get_all(key);
 
-        let mut headers: Vec<(String, String)> = Vec::new();
+        if values.is_empty() {
+            return None;
+        }
 
-        let mut h: Vec<&str> = self.header.lines().collect();
-        h.remove(0);
+        /*
+         * Cookie Lists are separated by ';'
+         */
+        match key.eq_ignore_ascii_case("cookie") {
+            true => Some(values.join("; ")),
+            false => Some(values.join(", ")),
+        }
+    }
+    /// Get Request Cookie
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let session: Option<String> = c.request.cookie("session").await;
+    ///     if session.is_none() {
+    ///         c.response.status = 401;
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub async fn cookie(&self, name: &str) -> Option<String> {
+        self.cookies()
+            .await
+            .into_iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v)
+    }
+    /// Get All Request Cookies
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     for (name, value) in c.request.cookies().await {
+    ///         println!("{} = {}", name, value);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub async fn cookies(&self) -> Vec<(String, String)> {
+        let mut cookies: Vec<(String, String)> = Vec::new();
 
-        h.into_iter().for_each(|ln: &str| {
-            let mut ln_split: Vec<String> =
-                ln.split_whitespace().map(|s: &str| s.to_owned()).collect();
+        for header in self.headers.get_all("cookie") {
+            for pair in header.split(';') {
+                let (k, v) = match pair.split_once('=') {
+                    Some(x) => x,
+                    None => continue,
+                };
 
-            if ln_split.is_empty() {
-                return;
-            }
-            /*
-             * Key
-             */
-            let k: String = ln_split[0].trim().replace(':', "");
-            /*
-             * Filter Key
-             */
-            let k: Vec<String> = k.split_whitespace().map(|s: &str| s.to_owned()).collect();
-            let k: String = k.join("");
-            if k.is_empty() {
-                return;
-            }
-            /*
-             * Value
-             */
-            let mut v: String = String::new();
-            ln_split.remove(0);
+                let k: &str = k.trim();
 
-            if !ln_split.is_empty() {
-                v = ln_split.join(" ");
-            }
+                if k.is_empty() {
+                    continue;
+                }
+                /*
+                 * Quoted Cookie Value
+                 */
+                let v: &str = v.trim();
+                let v: &str = v
+                    .strip_prefix('"')
+                    .and_then(|x: &str| x.strip_suffix('"'))
+                    .unwrap_or(v);
 
-            if k == key {
-                found_value = Some(v.clone());
+                cookies.push((k.to_owned(), v.to_owned()));
             }
+        }
 
-            headers.push((k, v));
-        });
-
-        self.header_store = headers;
-        found_value
+        cookies
+    }
+    /// Get Last-Event-ID
+    ///
+    /// ID of the last Server-Sent Event the client received, sent when it
+    /// reconnects.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     if let Some(id) = c.request.last_event_id().await {
+    ///         println!("Resume after: {}", id);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /events", route));
+    /// ```
+    pub async fn last_event_id(&self) -> Option<String> {
+        self.headers
+            .get("last-event-id")
+            .map(|v: &str| v.trim().to_owned())
     }
     /// Get Request Parameter
     ///
@@ -127,7 +239,7 @@ impl Request {
     ///
     /// async fn route(mut c: Context) -> Returns {
     ///     let user: String = c.request.param("user").await;
-    ///     c.response.body = format!("Username: {}", user);
+    ///     c.response.body = format!("Username: {}", user).into();
     ///     (c, None)
     /// }
     ///
@@ -136,10 +248,7 @@ impl Request {
     /// ```
     pub async fn param(&self, key: &str) -> String {
         let v: Option<String> = get_vec(&self.param_store, key.to_owned()).await;
-        match v {
-            Some(x) => x,
-            None => String::new(),
-        }
+        v.unwrap_or_default()
     }
     /// Get Request Query
     ///
@@ -151,9 +260,9 @@ impl Request {
     /// async fn route(mut c: Context) -> Returns {
     ///     let user: Option<String> = c.request.query("user").await;
     ///     match user {
-    ///         Some(u) => c.response.body = format!("Username: {}", u),
+    ///         Some(u) => c.response.body = format!("Username: {}", u).into(),
     ///         None => {
-    ///             c.response.body = format!("Username not found");
+    ///             c.response.body = format!("Username not found").into();
     ///             c.response.status = 404;
     ///         },
     ///     }
@@ -166,44 +275,408 @@ impl Request {
     /// app.add(route!("get /", route));
     /// ```
     pub async fn query(&mut self, key: &str) -> Option<String> {
-        if !self.query_store.is_empty() {
-            return get_vec(&self.query_store, key.to_owned()).await;
+        if self.query_store.is_empty() {
+            self.query_store = parse_urlencoded(&self.query).await;
         }
 
-        let mut found_value: Option<String> = None;
+        get_vec(&self.query_store, key.to_owned()).await
+    }
+    /// Get All Request Query Values
+    ///
+    /// Every value of a repeated key, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let tags: Vec<String> = c.request.query_all("tag").await;
+    ///     c.response.body = format!("Tags: {}", tags.join(", ")).into();
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    ///
+    /// /* Requested URL: /posts?tag=a&tag=b */
+    /// app.add(route!("get /posts", route));
+    /// ```
+    pub async fn query_all(&mut self, key: &str) -> Vec<String> {
+        if self.query_store.is_empty() {
+            self.query_store = parse_urlencoded(&self.query).await;
+        }
 
-        let query: String = self.query.clone();
+        self.query_store
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.to_owned())
+            .collect()
+    }
+    /// Get Typed Request Query
+    ///
+    /// Keys match fields ignoring case, like `query`. Repeated keys fill `Vec`
+    /// fields, add `#[serde(default)]` to keep them optional. Fails with `400`
+    /// describing the field that does not match the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, RequestError, route};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Search {
+    ///     q: String,
+    ///     page: Option<u32>,
+    ///     #[serde(default)]
+    ///     tag: Vec<String>,
+    /// }
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let search: Result<Search, RequestError> = c.request.query_as().await;
+    ///     match search {
+    ///         Ok(s) => c.response.body = format!("{} page {}", s.q, s.page.unwrap_or(1)).into(),
+    ///         Err(e) => c.response.error(e).await,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    ///
+    /// /* Requested URL: /search?q=rust&page=2&tag=web&tag=http */
+    /// app.add(route!("get /search", route));
+    /// ```
+    pub async fn query_as<T: DeserializeOwned>(&mut self) -> Result<T, RequestError> {
+        if self.query_store.is_empty() {
+            self.query_store = parse_urlencoded(&self.query).await;
+        }
 
-        if query.is_empty() {
-            return found_value;
+        deserialize_pairs(&self.query_store)
+            .await
+            .map_err(|e: PairsError| RequestError::new(400, &format!("Invalid query: {}", e)))
+    }
+    /// Get Typed Request Parameters
+    ///
+    /// Fails with `400` describing the parameter that does not match the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, RequestError, route};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Post {
+    ///     user: String,
+    ///     id: u64,
+    /// }
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let post: Result<Post, RequestError> = c.request.params_as().await;
+    ///     match post {
+    ///         Ok(p) => c.response.body = format!("Post {} of {}", p.id, p.user).into(),
+    ///         Err(e) => c.response.error(e).await,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /:user/posts/:id", route));
+    /// ```
+    pub async fn params_as<T: DeserializeOwned>(&self) -> Result<T, RequestError> {
+        deserialize_pairs(&self.param_store)
+            .await
+            .map_err(|e: PairsError| RequestError::new(400, &format!("Invalid parameter: {}", e)))
+    }
+    /// Get Request Body (Bytes)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let size: usize = c.request.bytes().await.len();
+    ///     c.response.body = format!("Received {} bytes", size).into();
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+    /// Get Request Body (UTF-8 Text)
+    ///
+    /// Returns `None` if the body is not valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let text: Option<String> = c.request.text().await;
+    ///     match text {
+    ///         Some(t) => c.response.body = format!("Message: {}", t).into(),
+    ///         None => c.response.status = 400,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /message", route));
+    /// ```
+    pub async fn text(&self) -> Option<String> {
+        String::from_utf8(self.body.clone()).ok()
+    }
+    /// Get Request Trailer
+    ///
+    /// Trailer fields sent after a `Transfer-Encoding: chunked` body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let checksum: Option<String> = c.request.trailer("x-checksum").await;
+    ///     match checksum {
+    ///         Some(x) => println!("Checksum is: {}", x),
+    ///         None => println!("Checksum not found"),
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn trailer(&self, key: &str) -> Option<String> {
+        self.trailers.get(key).map(|v: &str| v.to_owned())
+    }
+    /// Get JSON Request Body
+    ///
+    /// Requires a `Content-Type` of `application/json` (or `*/*+json`).
+    /// Fails with `415` for other content types and `400` for an invalid body.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, RequestError, route};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let user: Result<User, RequestError> = c.request.json().await;
+    ///     match user {
+    ///         Ok(u) => c.response.body = format!("{} is {} years old", u.name, u.age).into(),
+    ///         Err(e) => c.response.error(e).await,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /user", route));
+    /// ```
+    pub async fn json<T: DeserializeOwned>(&mut self) -> Result<T, RequestError> {
+        let content_type: String = self.header("content-type").await.unwrap_or_default();
+
+        let mime: String = parse_mime(&content_type).await;
+
+        if mime != "application/json" && !mime.ends_with("+json") {
+            return Err(RequestError::new(
+                415,
+                "Expected Content-Type: application/json",
+            ));
         }
 
-        let mut query_str: Vec<(String, String)> = Vec::new();
-        let query_split: Vec<String> = query.split('&').map(|s: &str| s.to_owned()).collect();
+        if self.body.is_empty() {
+            return Err(RequestError::new(400, "Request body is empty"));
+        }
 
-        query_split.iter().for_each(|q: &String| {
-            let mut kv: Vec<String> = q.split('=').map(|s: &str| s.to_owned()).collect();
+        serde_json::from_slice(&self.body)
+            .map_err(|e: serde_json::Error| RequestError::new(400, &format!("Invalid JSON: {}", e)))
+    }
+    /// Get Form Field
+    ///
+    /// Reads an `application/x-www-form-urlencoded` body or the text
+    /// fields of a `multipart/form-data` body.
+    /// Returns the first value of the field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let user: Option<String> = c.request.form("user").await;
+    ///     match user {
+    ///         Some(u) => c.response.body = format!("Username: {}", u).into(),
+    ///         None => c.response.status = 400,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /login", route));
+    /// ```
+    pub async fn form(&mut self, key: &str) -> Option<String> {
+        self.form_pairs().await.ok()?;
+        get_vec(&self.form_store, key.to_owned()).await
+    }
+    /// Get All Form Fields
+    ///
+    /// Every field in order, repeated fields included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     for (k, v) in c.request.form_all().await {
+    ///         println!("{} = {}", k, v);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /login", route));
+    /// ```
+    pub async fn form_all(&mut self) -> Vec<(String, String)> {
+        match self.form_pairs().await {
+            Ok(_) => self.form_store.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+    /// Get Typed Form Body
+    ///
+    /// Fails with `415` if the body is not a form and `400` if a field
+    /// does not match the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, RequestError, route};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     user: String,
+    ///     remember: Option<bool>,
+    ///     #[serde(default)]
+    ///     roles: Vec<String>,
+    /// }
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let login: Result<Login, RequestError> = c.request.form_as().await;
+    ///     match login {
+    ///         Ok(l) => c.response.body = format!("Username: {}", l.user).into(),
+    ///         Err(e) => c.response.error(e).await,
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /login", route));
+    /// ```
+    pub async fn form_as<T: DeserializeOwned>(&mut self) -> Result<T, RequestError> {
+        self.form_pairs().await?;
 
-            if kv.get(0).is_none() || kv[0].is_empty() {
-                return;
-            }
+        deserialize_pairs(&self.form_store)
+            .await
+            .map_err(|e: PairsError| RequestError::new(400, &format!("Invalid form: {}", e)))
+    }
+    /// Get multipart/form-data Parts
+    ///
+    /// Parts are parsed while the body is read. File parts are streamed to
+    /// the upload directory, see `Server::set_upload_dir`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(c: Context) -> Returns {
+    ///     for part in c.request.parts().await {
+    ///         match &part.filename {
+    ///             Some(f) => println!("File {}: {} ({} bytes)", part.name, f, part.size),
+    ///             None => println!("Field {}: {:?}", part.name, part.text().await),
+    ///         }
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+    /// Get multipart/form-data Part by Field Name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let saved: bool = match c.request.part("avatar").await {
+    ///         Some(p) => p.persist("/var/data/avatar.png").await.is_ok(),
+    ///         None => false,
+    ///     };
+    ///     if !saved {
+    ///         c.response.status = 400;
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /upload", route));
+    /// ```
+    pub async fn part(&self, name: &str) -> Option<&Part> {
+        self.parts.iter().find(|p: &&Part| p.name == name)
+    }
+    /*
+     * Parse Form Body once into Form Store
+     */
+    async fn form_pairs(&mut self) -> Result<(), RequestError> {
+        let content_type: String = self.header("content-type").await.unwrap_or_default();
 
-            let k: String = kv[0].to_owned();
-            let mut v: String = String::new();
-            kv.remove(0);
+        let mime: String = parse_mime(&content_type).await;
 
-            if !kv.is_empty() {
-                v = kv.join("=");
-            }
+        if !self.form_store.is_empty() {
+            return Ok(());
+        }
 
-            if k == key {
-                found_value = Some(v.clone());
+        match mime.as_str() {
+            "application/x-www-form-urlencoded" => {
+                let body: String = String::from_utf8_lossy(&self.body).to_string();
+                self.form_store = parse_urlencoded(&body).await;
             }
+            /*
+             * Text Fields of a multipart Body
+             */
+            "multipart/form-data" => {
+                for p in self.parts.iter().filter(|p: &&Part| p.filename.is_none()) {
+                    let value: String = String::from_utf8_lossy(&p.data).to_string();
+                    self.form_store.push((p.name.to_owned(), value));
+                }
+            }
+            _ => {
+                return Err(RequestError::new(
+                    415,
+                    "Expected Content-Type: application/x-www-form-urlencoded",
+                ))
+            }
+        }
 
-            query_str.push((k, v));
-        });
-
-        self.query_store = query_str;
-        found_value
+        Ok(())
     }
 }
@@ -1,13 +1,34 @@
-use crate::utils::del_vec::del_vec;
-use crate::utils::get_vec::get_vec;
-use crate::utils::set_vec::set_vec;
+use crate::structs::body::Body;
+use crate::structs::cookie::Cookie;
+use crate::structs::definition::OnUpgrade;
+use crate::structs::error::RequestError;
+use crate::structs::headers::Headers;
+use crate::structs::sse::Sse;
+use crate::utils::status_string::status_string;
 
 use serde::Serialize;
 use serde_json::Error;
+use std::time::UNIX_EPOCH;
 
 #[derive(Clone, Debug)]
 pub struct Response {
-    pub(crate) header: Vec<(String, String)>,
+    /// Get & Set Response Header Fields
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     c.response.headers.append("Link", "</style.css>; rel=preload");
+    ///     c.response.headers.append("Link", "</app.js>; rel=preload");
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub headers: Headers,
     /// Get & Set Response Body
     ///
     /// # Example
@@ -17,18 +38,18 @@ pub struct Response {
     ///
     /// async fn route(mut c: Context) -> Returns {
     ///     /* Get */
-    ///     let body: String = c.response.body;
-    ///     println!("{}", body);
+    ///     let body: Option<String> = c.response.body.text();
+    ///     println!("{:?}", body);
     ///
     ///     /* Set */
-    ///     c.response.body = "<h1>Hello World</h1>".to_owned();
+    ///     c.response.body = "<h1>Hello World</h1>".into();
     ///     (c, None)
     /// }
     ///
     /// let mut app = Server::new();
     /// app.add(route!("get /", route));
     /// ```
-    pub body: String,
+    pub body: Body,
     /// Get & Set Response Status
     ///
     /// # Example
@@ -71,6 +92,7 @@ pub struct Response {
     /// app.add(route!("get /", route));
     /// ```
     pub content_type: String,
+    pub(crate) upgrade: Option<OnUpgrade>,
 }
 
 impl Response {
@@ -103,16 +125,55 @@ impl Response {
     /// app.add(route!("get /", route));
     /// ```
     pub async fn json(&mut self, value: impl Serialize) {
-        let value: Result<String, Error> = serde_json::to_string(&value);
+        let value: Result<Vec<u8>, Error> = serde_json::to_vec(&value);
         match value {
-            Ok(s) => self.body = s,
+            Ok(s) => self.body = s.into(),
             Err(e) => {
-                self.body = "{}".to_owned();
+                self.body = "{}".into();
                 println!("[Error] Fail to serialize json data:\n{}", e);
             }
         }
         self.content_type = "application/json".to_owned();
     }
+    /// Set Error Response
+    ///
+    /// Sets the status of the error & a JSON body describing it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, RequestError, route};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     name: String,
+    /// }
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let user: Result<User, RequestError> = c.request.json().await;
+    ///     if let Err(e) = user {
+    ///         /* {"status":400,"error":"Bad Request","message":"..."} */
+    ///         c.response.error(e).await;
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /user", route));
+    /// ```
+    pub async fn error(&mut self, error: RequestError) {
+        let status_str: String = status_string(error.status).await;
+
+        self.json(serde_json::json!({
+            "status": error.status,
+            "error": status_str,
+            "message": error.message,
+        }))
+        .await;
+
+        self.status = error.status;
+    }
     /// Get Response Header
     ///
     /// # Example
@@ -134,10 +195,13 @@ impl Response {
     /// app.add(route!("get /", route));
     /// ```
     pub async fn get_header(&self, key: &str) -> Option<String> {
-        get_vec(&self.header, key.to_owned()).await
+        self.headers.get(key).map(|v: &str| v.to_owned())
     }
     /// Set Response Header
     ///
+    /// `Content-Length` & `Transfer-Encoding` follow from the body, values
+    /// set for them are not sent.
+    ///
     /// # Example
     ///
     /// ```
@@ -152,7 +216,7 @@ impl Response {
     /// app.add(route!("get /", route));
     /// ```
     pub async fn set_header(&mut self, key: &str, value: &str) {
-        self.header = set_vec(&self.header, key.to_owned(), value.to_owned()).await;
+        self.headers.insert(key, value);
     }
     /// Delete Response Header
     ///
@@ -170,6 +234,104 @@ impl Response {
     /// app.add(route!("get /", route));
     /// ```
     pub async fn del_header(&mut self, key: &str) {
-        self.header = del_vec(&self.header, key.to_owned()).await;
+        self.headers.remove(key);
+    }
+    /// Set Response Cookie
+    ///
+    /// Every Cookie is sent as its own `Set-Cookie` header. Fails (and sets
+    /// nothing) if the name is not a token, the value contains characters
+    /// not allowed in a cookie (e.g. `;`, `,`, whitespace) or `path` /
+    /// `domain` contain `;` or control characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, Cookie, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let mut session: Cookie = Cookie::new("session", "abc123");
+    ///     session.http_only = true;
+    ///
+    ///     if let Err(e) = c.response.set_cookie(session).await {
+    ///         println!("Invalid Cookie: {}", e);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /", route));
+    /// ```
+    pub async fn set_cookie(&mut self, cookie: Cookie) -> std::io::Result<()> {
+        cookie.check()?;
+
+        self.headers.append("Set-Cookie", &cookie.to_string());
+
+        Ok(())
+    }
+    /// Remove Client Cookie
+    ///
+    /// Expires the Cookie on the client. `path` & `domain` must match the
+    /// ones the Cookie was set with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, Cookie, route};
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let mut session: Cookie = Cookie::new("session", "");
+    ///     session.path = Some("/".to_owned());
+    ///
+    ///     if let Err(e) = c.response.remove_cookie(session).await {
+    ///         println!("Invalid Cookie: {}", e);
+    ///     }
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("post /logout", route));
+    /// ```
+    pub async fn remove_cookie(&mut self, mut cookie: Cookie) -> std::io::Result<()> {
+        cookie.value = String::new();
+        cookie.max_age = Some(0);
+        cookie.expires = Some(UNIX_EPOCH);
+        self.set_cookie(cookie).await
+    }
+    /// Send Server-Sent Events
+    ///
+    /// The connection stays open until the events end or the client
+    /// disconnects.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, Event, Sse, route};
+    /// use futures::stream;
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     /* Resume after the last Event the client got */
+    ///     let from: usize = match c.request.last_event_id().await {
+    ///         Some(id) => id.parse::<usize>().unwrap_or(0) + 1,
+    ///         None => 0,
+    ///     };
+    ///
+    ///     let events = stream::iter((from..10).map(|i: usize| {
+    ///         let mut e: Event = Event::new(&format!("update {}", i));
+    ///         e.id = Some(i.to_string());
+    ///         e
+    ///     }));
+    ///
+    ///     c.response.sse(Sse::new(events)).await;
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /events", route));
+    /// ```
+    pub async fn sse(&mut self, sse: Sse) {
+        self.status = 200;
+        self.content_type = "text/event-stream".to_owned();
+        self.headers.insert("Cache-Control", "no-cache");
+        self.body = Body::stream(sse.frames());
     }
 }
@@ -0,0 +1,298 @@
+use crate::structs::handoff::Handoff;
+use crate::structs::stats::Stats;
+use std::net::SocketAddr;
+use std::process::{Child, Command};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::io::Error;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::sync::watch::Sender;
+use tokio::task::JoinHandle;
+
+/// Handle of a Server running in the background
+///
+/// Dropping the handle leaves the server running.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, ServerHandle};
+///
+/// async fn start() -> ServerHandle {
+///     let app = Server::new();
+///     let bound = app.bind("127.0.0.1:0").await.unwrap();
+///     bound.serve()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ServerHandle {
+    pub(crate) local_addrs: Vec<SocketAddr>,
+    pub(crate) stop: Sender<bool>,
+    pub(crate) handoff: mpsc::Sender<Handoff>,
+    pub(crate) task: JoinHandle<()>,
+    pub(crate) stats: Arc<Stats>,
+}
+
+impl ServerHandle {
+    /// Get Local Address of the first TCP Listener
+    ///
+    /// `None` with only Unix sockets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// fn url(handle: &ServerHandle) -> String {
+    ///     format!("http://{}/", handle.local_addr().unwrap())
+    /// }
+    /// ```
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addrs.first().copied()
+    }
+    /// Get Local Addresses of all TCP Listeners
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// fn print(handle: &ServerHandle) {
+    ///     for address in handle.local_addrs() {
+    ///         println!("Listening on {}", address);
+    ///     }
+    /// }
+    /// ```
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.local_addrs
+    }
+    /// Stop the Server (Graceful Shutdown)
+    ///
+    /// Returns at once, `join` waits until the connections are drained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// async fn restart(handle: ServerHandle) {
+    ///     handle.stop();
+    ///     handle.join().await;
+    /// }
+    /// ```
+    pub fn stop(&self) {
+        let _ = self.stop.send(true);
+    }
+    /// Hand the Listeners over to a new Process (Zero-Downtime Upgrade)
+    ///
+    /// Starts the command with the listening sockets as descriptors 3, 4, ...
+    /// (in the order they were added) with `LISTEN_FDS` and
+    /// `OXIDY_LISTEN_SOCKETS` set, so `Server::bind_systemd` or `listen_fds`
+    /// of the new process takes them over. Then this server stops accepting
+    /// and shuts down gracefully, `join` waits until its connections are
+    /// drained. Unix socket files are left to the new process.
+    ///
+    /// On error (e.g. the program does not exist) the server keeps running.
+    /// Connections wait in the socket backlog until the new process accepts,
+    /// so it should get ready quickly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use oxidy::{Server, shutdown_signal};
+    /// use tokio::signal::unix::{signal, SignalKind};
+    ///
+    /// async fn start() -> std::io::Result<()> {
+    ///     let app = Server::new();
+    ///
+    ///     /* Sockets of the old Process, or new ones on the first Start */
+    ///     let bound = match app.bind_systemd() {
+    ///         Ok(x) => x,
+    ///         Err(_) => app.bind("0.0.0.0:3000").await?,
+    ///     };
+    ///     let handle = bound.serve();
+    ///
+    ///     /* Re-execute the (replaced) Binary on SIGUSR2, stop on SIGTERM */
+    ///     let mut upgrade = signal(SignalKind::user_defined2())?;
+    ///
+    ///     loop {
+    ///         tokio::select! {
+    ///             _ = upgrade.recv() => {
+    ///                 let mut args = std::env::args_os();
+    ///                 let mut command = Command::new(args.next().unwrap());
+    ///                 command.args(args);
+    ///
+    ///                 match handle.upgrade(command).await {
+    ///                     Ok(child) => break println!("Handed over to {}", child.id()),
+    ///                     Err(e) => println!("Upgrade failed: {}", e),
+    ///                 }
+    ///             }
+    ///             _ = shutdown_signal() => break handle.stop(),
+    ///         }
+    ///     }
+    ///
+    ///     handle.join().await;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn upgrade(&self, command: Command) -> Result<Child, Error> {
+        let (reply, replied) = oneshot::channel();
+
+        let handoff: Handoff = Handoff { command, reply };
+
+        if self.handoff.send(handoff).await.is_err() {
+            return Err(Error::other("Server is not running"));
+        }
+
+        match replied.await {
+            Ok(x) => x,
+            Err(_) => Err(Error::other("Server is not running")),
+        }
+    }
+    /// Wait until the Server has stopped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// async fn wait(handle: ServerHandle) {
+    ///     handle.join().await;
+    ///     println!("Server stopped");
+    /// }
+    /// ```
+    pub async fn join(self) {
+        if let Err(e) = self.task.await {
+            println!("[Error] Server Task failed:\n{}", e);
+        }
+    }
+    /// Get Number of accepted Connections
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// fn report(handle: &ServerHandle) {
+    ///     println!(
+    ///         "Connections: {} ({} open), Requests: {}",
+    ///         handle.accepted(),
+    ///         handle.connections(),
+    ///         handle.requests()
+    ///     );
+    /// }
+    /// ```
+    pub fn accepted(&self) -> usize {
+        self.stats.accepted.load(Ordering::Relaxed)
+    }
+    /// Get Number of open Connections
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// fn idle(handle: &ServerHandle) -> bool {
+    ///     handle.connections() == 0
+    /// }
+    /// ```
+    pub fn connections(&self) -> usize {
+        self.stats.active.load(Ordering::Relaxed)
+    }
+    /// Get Number of served Requests (HTTP/2 Streams included)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::ServerHandle;
+    ///
+    /// fn served(handle: &ServerHandle) -> usize {
+    ///     handle.requests()
+    /// }
+    /// ```
+    pub fn requests(&self) -> usize {
+        self.stats.requests.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::Server;
+    use crate::structs::context::Context;
+    use crate::structs::definition::{Callback, Returns};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::time::{sleep, timeout};
+
+    async fn slow(mut c: Context) -> Returns {
+        sleep(Duration::from_millis(300)).await;
+        c.response.body = "done".into();
+        (c, None)
+    }
+
+    fn app() -> Server {
+        let mut app: Server = Server::new();
+        let route: Arc<Callback> = Arc::new(Box::new(|c: Context| Box::pin(slow(c))));
+        app.add(("get", "/", vec![route]));
+        app
+    }
+
+    async fn get(address: SocketAddr) -> String {
+        let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: a\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut response: String = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn stop_join() {
+        let handle: ServerHandle = app().bind("127.0.0.1:0").await.unwrap().serve();
+
+        let address: SocketAddr = handle.local_addr().unwrap();
+        assert_ne!(address.port(), 0);
+        assert_eq!(handle.local_addrs(), [address]);
+
+        assert!(get(address).await.ends_with("\r\n\r\ndone"));
+        assert_eq!(handle.accepted(), 1);
+        assert_eq!(handle.requests(), 1);
+
+        handle.stop();
+        timeout(Duration::from_secs(5), handle.join())
+            .await
+            .unwrap();
+        /*
+         * Listener is closed
+         */
+        assert!(TcpStream::connect(address).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stop_drains() {
+        let handle: ServerHandle = app().bind("127.0.0.1:0").await.unwrap().serve();
+        let address: SocketAddr = handle.local_addr().unwrap();
+        /*
+         * Request running when the Server stops
+         */
+        let request = tokio::spawn(get(address));
+        sleep(Duration::from_millis(100)).await;
+
+        handle.stop();
+        timeout(Duration::from_secs(5), handle.join())
+            .await
+            .unwrap();
+
+        let response: String = request.await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\ndone"));
+    }
+}
@@ -0,0 +1,29 @@
+use tokio::sync::watch::{channel, Receiver, Sender};
+
+/*
+ * Shutdown Signal of a running Server, shared by all its Connections
+ */
+#[derive(Clone, Debug)]
+pub(crate) struct Shutdown {
+    receiver: Receiver<bool>,
+}
+
+impl Shutdown {
+    pub(crate) fn new() -> (Sender<bool>, Shutdown) {
+        let (sender, receiver) = channel(false);
+
+        (sender, Shutdown { receiver })
+    }
+    /*
+     * Shutdown started
+     */
+    pub(crate) fn is_set(&self) -> bool {
+        *self.receiver.borrow()
+    }
+    /*
+     * Wait until Shutdown starts (or the Server is gone)
+     */
+    pub(crate) async fn wait(&mut self) {
+        let _ = self.receiver.wait_for(|x: &bool| *x).await;
+    }
+}
@@ -0,0 +1,181 @@
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt};
+use std::fmt::{Display, Formatter, Result};
+use std::io::Error;
+use std::time::Duration;
+use tokio::sync::mpsc::{channel, Receiver, Sender};
+use tokio::time::{interval_at, Instant, Interval};
+
+/// Server-Sent Event
+///
+/// # Example
+///
+/// ```
+/// use oxidy::Event;
+///
+/// let mut event: Event = Event::new("{\"cpu\":42}");
+/// event.event = Some("stats".to_owned());
+/// event.id = Some("17".to_owned());
+/// event.retry = Some(5000);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Event {
+    /// Event Type (`message` when not set)
+    pub event: Option<String>,
+    /// Event ID, sent back by the client as `Last-Event-ID` on reconnect
+    pub id: Option<String>,
+    /// Event Data, multiple lines are sent as multiple `data:` fields
+    pub data: String,
+    /// Client Reconnection Time in Milliseconds
+    pub retry: Option<u64>,
+}
+
+impl Event {
+    /// New Event with Data
+    pub fn new(data: &str) -> Event {
+        Event {
+            data: data.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/*
+ * text/event-stream Frame
+ */
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        /*
+         * Line breaks would start a new Field
+         */
+        let clean = |x: &str| -> String { x.replace(['\r', '\n', '\0'], "") };
+
+        if let Some(x) = &self.event {
+            writeln!(f, "event: {}", clean(x))?;
+        }
+
+        if let Some(x) = &self.id {
+            writeln!(f, "id: {}", clean(x))?;
+        }
+
+        if let Some(x) = self.retry {
+            writeln!(f, "retry: {}", x)?;
+        }
+
+        for line in self.data.replace("\r\n", "\n").split(['\n', '\r']) {
+            writeln!(f, "data: {}", line)?;
+        }
+
+        writeln!(f)
+    }
+}
+
+/// Server-Sent Events Response
+///
+/// Sent with `Response::sse`. Events come from a stream or a channel; the
+/// stream ends the response when it ends.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, Event, Sse, route};
+/// use std::time::Duration;
+///
+/// async fn route(mut c: Context) -> Returns {
+///     let (sender, mut sse) = Sse::channel(16);
+///     sse.keep_alive = Some(Duration::from_secs(10));
+///
+///     tokio::spawn(async move {
+///         let mut i: u64 = 0;
+///         /* Fails once the client is gone */
+///         while sender.send(Event::new(&format!("tick {}", i))).await.is_ok() {
+///             tokio::time::sleep(Duration::from_secs(1)).await;
+///             i += 1;
+///         }
+///     });
+///
+///     c.response.sse(sse).await;
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("get /events", route));
+/// ```
+pub struct Sse {
+    events: BoxStream<'static, Event>,
+    /// Interval of Keep-Alive Comments sent while no Event is sent
+    ///
+    /// Keeps proxies from closing the idle connection & detects gone
+    /// clients. Default: 15 seconds.
+    pub keep_alive: Option<Duration>,
+}
+
+impl Sse {
+    /// Events from a Stream
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::{Server, Context, Returns, Event, Sse, route};
+    /// use futures::stream;
+    ///
+    /// async fn route(mut c: Context) -> Returns {
+    ///     let events = stream::iter(vec![Event::new("one"), Event::new("two")]);
+    ///     c.response.sse(Sse::new(events)).await;
+    ///     (c, None)
+    /// }
+    ///
+    /// let mut app = Server::new();
+    /// app.add(route!("get /events", route));
+    /// ```
+    pub fn new(events: impl Stream<Item = Event> + Send + 'static) -> Sse {
+        Sse {
+            events: events.boxed(),
+            keep_alive: Some(Duration::from_secs(15)),
+        }
+    }
+    /// Events from a Channel
+    ///
+    /// Sending fails & `Sender::closed` resolves once the client disconnects.
+    pub fn channel(buffer: usize) -> (Sender<Event>, Sse) {
+        let (sender, receiver) = channel(buffer);
+
+        let events = stream::unfold(receiver, |mut r: Receiver<Event>| async move {
+            r.recv().await.map(|e: Event| (e, r))
+        });
+
+        (sender, Sse::new(events))
+    }
+    /*
+     * Frames with Keep-Alive Comments in between
+     */
+    pub(crate) fn frames(self) -> BoxStream<'static, std::result::Result<Vec<u8>, Error>> {
+        let period: Duration = match self.keep_alive {
+            Some(x) if !x.is_zero() => x,
+            _ => {
+                return self
+                    .events
+                    .map(|e: Event| Ok(e.to_string().into_bytes()))
+                    .boxed();
+            }
+        };
+
+        let timer: Interval = interval_at(Instant::now() + period, period);
+
+        stream::unfold(
+            (self.events, timer),
+            |(mut events, mut timer): (BoxStream<'static, Event>, Interval)| async move {
+                let frame: String = tokio::select! {
+                    e = events.next() => {
+                        timer.reset();
+                        e?.to_string()
+                    }
+                    _ = timer.tick() => ":\n\n".to_owned(),
+                };
+
+                Some((Ok(frame.into_bytes()), (events, timer)))
+            },
+        )
+        .boxed()
+    }
+}
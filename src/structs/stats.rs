@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/*
+ * Counters of a running Server
+ */
+#[derive(Debug, Default)]
+pub(crate) struct Stats {
+    pub(crate) accepted: AtomicUsize,
+    pub(crate) active: AtomicUsize,
+    pub(crate) requests: AtomicUsize,
+}
+
+impl Stats {
+    /*
+     * Count an accepted Connection as active until the Guard is dropped
+     * (also when its Task is aborted)
+     */
+    pub(crate) fn connection(self: &Arc<Stats>) -> ActiveGuard {
+        self.accepted.fetch_add(1, Ordering::Relaxed);
+        self.active.fetch_add(1, Ordering::Relaxed);
+
+        ActiveGuard(self.clone())
+    }
+}
+
+pub(crate) struct ActiveGuard(Arc<Stats>);
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::structs::cert_resolver::CertResolver;
+use crate::utils::load_certified_key::load_certified_key;
+use rustls::crypto::ring::default_provider;
+use rustls::crypto::CryptoProvider;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::Error;
+
+/// Client Certificate Verification (Mutual TLS)
+///
+/// The CA bundle is read once when the listener starts, `TlsConfig::reload`
+/// and `reload_interval` do not pick up a changed bundle.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{ClientAuth, TlsConfig};
+///
+/// let mut tls = TlsConfig::new("cert.pem", "key.pem");
+/// tls.client_auth = ClientAuth::Required("clients-ca.pem".into());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub enum ClientAuth {
+    /// No Client Certificate requested
+    #[default]
+    None,
+    /// Client Certificate verified against the CA Bundle (PEM) if sent
+    Optional(PathBuf),
+    /// Client Certificate verified against the CA Bundle (PEM), Handshake fails without
+    Required(PathBuf),
+}
+
+/// TLS Settings of `Server::run_tls`
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, TlsConfig};
+///
+/// let app = Server::new();
+/// let tls = TlsConfig::new("cert.pem", "key.pem");
+/// /* app.run_tls("127.0.0.1:3443", tls).await; */
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    /// PEM File with the Certificate Chain (Leaf first)
+    pub cert_path: PathBuf,
+    /// PEM File with the Private Key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: PathBuf,
+    /// Client Certificate Verification, default is `ClientAuth::None`
+    pub client_auth: ClientAuth,
+    /// Check Certificate & Key File for Changes and reload them, default is `None` (no watching)
+    pub reload_interval: Option<Duration>,
+    /*
+     * Shared by Clones, so a kept Clone reloads the running Listener
+     */
+    pub(crate) resolver: Arc<CertResolver>,
+}
+
+impl TlsConfig {
+    /// New TLS Settings from PEM Files
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxidy::TlsConfig;
+    ///
+    /// let tls = TlsConfig::new("/etc/ssl/example.com/fullchain.pem", "/etc/ssl/example.com/key.pem");
+    /// ```
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> TlsConfig {
+        TlsConfig {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_auth: ClientAuth::None,
+            reload_interval: None,
+            resolver: Arc::new(CertResolver::default()),
+        }
+    }
+    /// Reload Certificate Chain & Private Key
+    ///
+    /// New connections get the new certificate, established ones keep the
+    /// old one. On error the current certificate stays in use. Clones share
+    /// the certificate, so keep a clone of the config given to `run_tls`.
+    ///
+    /// Only the certificate chain & key are reloaded, a new client CA bundle
+    /// (`ClientAuth`) takes a restart or `ServerHandle::upgrade`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use oxidy::{Server, TlsConfig};
+    ///
+    /// let app = Server::new();
+    ///
+    /// /* Watch the Files */
+    /// let mut tls = TlsConfig::new("cert.pem", "key.pem");
+    /// tls.reload_interval = Some(Duration::from_secs(60));
+    ///
+    /// /* Or reload on demand (e.g. on SIGHUP) */
+    /// let handle = tls.clone();
+    /// /* app.run_tls("127.0.0.1:3443", tls).await; */
+    /// /* Elsewhere: handle.reload().await.unwrap(); */
+    /// ```
+    pub async fn reload(&self) -> Result<(), Error> {
+        let provider: CryptoProvider = default_provider();
+
+        self.resolver
+            .set(load_certified_key(self, &provider).await?);
+
+        Ok(())
+    }
+}
+
+/// TLS Session of a Request
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, route};
+///
+/// async fn route(c: Context) -> Returns {
+///     if let Some(tls) = &c.request.tls {
+///         println!("ALPN: {:?}, SNI: {:?}", tls.protocol, tls.server_name);
+///     }
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(route!("get /", route));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TlsInfo {
+    /// Negotiated ALPN Protocol (`h2` or `http/1.1`)
+    pub protocol: Option<String>,
+    /// Server Name sent by the Client (SNI)
+    pub server_name: Option<String>,
+    /// Client Certificate Chain as sent by the client (Leaf first), empty
+    /// without Mutual TLS. Only the Leaf is known to be verified against the
+    /// CA bundle, further certificates are not checked on their own.
+    pub peer_certificates: Vec<PeerCertificate>,
+}
+
+/// Client Certificate of a Mutual TLS Session
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Returns, middleware};
+///
+/// async fn mid(mut c: Context) -> Returns {
+///     let allowed: bool = c
+///         .request
+///         .tls
+///         .as_ref()
+///         .and_then(|tls| tls.peer_certificates.first())
+///         .map(|cert| cert.sans.iter().any(|san| san == "billing.internal"))
+///         .unwrap_or(false);
+///
+///     if !allowed {
+///         c.response.status = 403;
+///         c.response.body = "Forbidden".into();
+///         c.next = false;
+///     }
+///     (c, None)
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(middleware!(mid));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PeerCertificate {
+    /// Subject Distinguished Name (e.g. `CN=billing.internal, O=Example`)
+    pub subject: String,
+    /// Issuer Distinguished Name
+    pub issuer: String,
+    /// Subject Alternative Names (DNS Names, IP Addresses, Emails & URIs)
+    pub sans: Vec<String>,
+    /// SHA-256 Fingerprint of the DER Certificate (lowercase hex)
+    pub fingerprint: String,
+    /// DER encoded Certificate
+    pub der: Vec<u8>,
+}
@@ -0,0 +1,634 @@
+use crate::structs::context::Context;
+use crate::structs::definition::{IoStream, OnUpgrade, Returns, Upgrade};
+use crate::structs::error::RequestError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/*
+ * RFC 6455 Handshake GUID
+ */
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// WebSocket Message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    /// Answered with a Pong automatically
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    /// Close Code & Reason
+    Close(Option<(u16, String)>),
+}
+
+/// WebSocket Connection
+///
+/// Handed to `websocket!` handlers after the handshake. Fragmented messages
+/// are joined before `recv` returns them; messages larger than the maximum
+/// body size (`Server::set_max_body_size`) close the connection with 1009.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, Context, Message, WebSocket, websocket};
+///
+/// async fn echo(_: Context, mut ws: WebSocket) {
+///     while let Some(message) = ws.recv().await {
+///         match message {
+///             Message::Text(t) => {
+///                 if ws.send(Message::Text(t)).await.is_err() {
+///                     break;
+///                 }
+///             }
+///             Message::Binary(b) => {
+///                 if ws.send(Message::Binary(b)).await.is_err() {
+///                     break;
+///                 }
+///             }
+///             _ => {}
+///         }
+///     }
+/// }
+///
+/// let mut app = Server::new();
+/// app.add(websocket!("/echo", echo));
+/// ```
+pub struct WebSocket {
+    stream: IoStream,
+    read: Vec<u8>,
+    write: Vec<u8>,
+    fragments: Option<(u8, Vec<u8>)>,
+    max_message_size: usize,
+    close_sent: bool,
+    closed: bool,
+}
+
+impl WebSocket {
+    pub(crate) fn new(stream: IoStream, read: Vec<u8>, max_message_size: usize) -> WebSocket {
+        WebSocket {
+            stream,
+            read,
+            write: Vec::new(),
+            fragments: None,
+            max_message_size,
+            close_sent: false,
+            closed: false,
+        }
+    }
+    /// Receive next Message
+    ///
+    /// `None` once the connection is closed. Safe to use in `tokio::select!`,
+    /// partially received frames are kept for the next call.
+    pub async fn recv(&mut self) -> Option<Message> {
+        loop {
+            if self.closed {
+                return None;
+            }
+
+            if self.flush().await.is_err() {
+                self.closed = true;
+                return None;
+            }
+
+            let frame: Option<(bool, u8, Vec<u8>)> = match self.parse_frame() {
+                Ok(x) => x,
+                Err(code) => {
+                    self.fail(code).await;
+                    return None;
+                }
+            };
+            /*
+             * Incomplete Frame: read more
+             */
+            let (fin, opcode, payload) = match frame {
+                Some(x) => x,
+                None => {
+                    let mut chunk: [u8; 8192] = [0; 8192];
+
+                    match self.stream.read(&mut chunk).await {
+                        Ok(0) | Err(_) => {
+                            self.closed = true;
+                            return None;
+                        }
+                        Ok(x) => self.read.extend_from_slice(&chunk[..x]),
+                    }
+
+                    continue;
+                }
+            };
+
+            let message: Option<Message> = match self.on_frame(fin, opcode, payload) {
+                Ok(x) => x,
+                Err(code) => {
+                    self.fail(code).await;
+                    return None;
+                }
+            };
+            /*
+             * Close Handshake done: answer is queued, end the connection
+             */
+            if let Some(Message::Close(_)) = message {
+                let _ = self.flush().await;
+                let _ = self.stream.shutdown().await;
+                self.closed = true;
+            }
+
+            if message.is_some() {
+                return message;
+            }
+        }
+    }
+    /// Send Message
+    ///
+    /// Sending `Message::Close` starts the close handshake, keep calling
+    /// `recv` until it returns `None` to receive the answer.
+    ///
+    /// Fails with `InvalidInput` when a Ping / Pong payload is longer than
+    /// 125 bytes or a Close reason longer than 123 bytes.
+    pub async fn send(&mut self, message: Message) -> Result<(), Error> {
+        if self.close_sent || self.closed {
+            return Err(Error::new(ErrorKind::BrokenPipe, "WebSocket closed"));
+        }
+        /*
+         * Control Frames carry at most 125 bytes (Close: Code & Reason)
+         */
+        let control: usize = match &message {
+            Message::Ping(x) | Message::Pong(x) => x.len(),
+            Message::Close(Some((_, reason))) => 2 + reason.len(),
+            _ => 0,
+        };
+
+        if control > 125 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "WebSocket control frame payload longer than 125 bytes",
+            ));
+        }
+
+        match message {
+            Message::Text(x) => self.queue(0x1, x.as_bytes()),
+            Message::Binary(x) => self.queue(0x2, &x),
+            Message::Ping(x) => self.queue(0x9, &x),
+            Message::Pong(x) => self.queue(0xA, &x),
+            Message::Close(x) => self.queue_close(x),
+        }
+
+        self.flush().await
+    }
+    /// Close the Connection with Code & Reason
+    pub async fn close(&mut self, code: u16, reason: &str) -> Result<(), Error> {
+        self.send(Message::Close(Some((code, reason.to_owned()))))
+            .await
+    }
+    /*
+     * Parse one Frame from the read buffer
+     * Err is the Close Code to fail the connection with
+     */
+    fn parse_frame(&mut self) -> Result<Option<(bool, u8, Vec<u8>)>, u16> {
+        if self.read.len() < 2 {
+            return Ok(None);
+        }
+
+        let fin: bool = self.read[0] & 0x80 != 0;
+        let rsv: u8 = self.read[0] & 0x70;
+        let opcode: u8 = self.read[0] & 0x0F;
+        let masked: bool = self.read[1] & 0x80 != 0;
+        /*
+         * No Extensions negotiated & Client Frames must be masked
+         */
+        if rsv != 0 || !masked {
+            return Err(1002);
+        }
+
+        let (length, offset): (u64, usize) = match self.read[1] & 0x7F {
+            126 => {
+                if self.read.len() < 4 {
+                    return Ok(None);
+                }
+                (u16::from_be_bytes([self.read[2], self.read[3]]) as u64, 4)
+            }
+            127 => {
+                if self.read.len() < 10 {
+                    return Ok(None);
+                }
+                let mut bytes: [u8; 8] = [0; 8];
+                bytes.copy_from_slice(&self.read[2..10]);
+                (u64::from_be_bytes(bytes), 10)
+            }
+            x => (x as u64, 2),
+        };
+
+        if length > self.max_message_size as u64 {
+            return Err(1009);
+        }
+
+        let length: usize = length as usize;
+
+        if self.read.len() < offset + 4 + length {
+            return Ok(None);
+        }
+
+        let mask: [u8; 4] = [
+            self.read[offset],
+            self.read[offset + 1],
+            self.read[offset + 2],
+            self.read[offset + 3],
+        ];
+
+        let start: usize = offset + 4;
+
+        let payload: Vec<u8> = self.read[start..start + length]
+            .iter()
+            .enumerate()
+            .map(|(i, b): (usize, &u8)| b ^ mask[i % 4])
+            .collect();
+
+        self.read.drain(..start + length);
+
+        Ok(Some((fin, opcode, payload)))
+    }
+    /*
+     * Handle one Frame, returns a Message once one is complete
+     */
+    fn on_frame(
+        &mut self,
+        fin: bool,
+        opcode: u8,
+        payload: Vec<u8>,
+    ) -> Result<Option<Message>, u16> {
+        /*
+         * Control Frames: not fragmented, at most 125 bytes
+         */
+        if opcode >= 0x8 && (!fin || payload.len() > 125) {
+            return Err(1002);
+        }
+
+        match opcode {
+            0x8 => {
+                let close: Option<(u16, String)> = match payload.len() {
+                    0 => None,
+                    1 => return Err(1002),
+                    _ => {
+                        let code: u16 = u16::from_be_bytes([payload[0], payload[1]]);
+                        /*
+                         * Codes allowed on the wire (RFC 6455 7.4), others
+                         * are reserved or only for local use
+                         */
+                        if !matches!(code, 1000..=1003 | 1007..=1014 | 3000..=4999) {
+                            return Err(1002);
+                        }
+
+                        let reason: String = match String::from_utf8(payload[2..].to_vec()) {
+                            Ok(x) => x,
+                            Err(_) => return Err(1007),
+                        };
+
+                        Some((code, reason))
+                    }
+                };
+                /*
+                 * Answer the Close with the same Code
+                 */
+                if !self.close_sent {
+                    self.queue_close(close.as_ref().map(|(code, _)| (*code, String::new())));
+                }
+
+                Ok(Some(Message::Close(close)))
+            }
+            0x9 => {
+                self.queue(0xA, &payload);
+                Ok(Some(Message::Ping(payload)))
+            }
+            0xA => Ok(Some(Message::Pong(payload))),
+            0x1 | 0x2 => {
+                if self.fragments.is_some() {
+                    return Err(1002);
+                }
+
+                if fin {
+                    return message(opcode, payload).map(Some);
+                }
+
+                self.fragments = Some((opcode, payload));
+                Ok(None)
+            }
+            0x0 => {
+                let (first, mut data) = match self.fragments.take() {
+                    Some(x) => x,
+                    None => return Err(1002),
+                };
+
+                if data.len() + payload.len() > self.max_message_size {
+                    return Err(1009);
+                }
+
+                data.extend_from_slice(&payload);
+
+                if fin {
+                    return message(first, data).map(Some);
+                }
+
+                self.fragments = Some((first, data));
+                Ok(None)
+            }
+            _ => Err(1002),
+        }
+    }
+    /*
+     * Queue an unmasked Server Frame
+     */
+    fn queue(&mut self, opcode: u8, payload: &[u8]) {
+        self.write.push(0x80 | opcode);
+
+        match payload.len() {
+            x if x < 126 => self.write.push(x as u8),
+            x if x <= u16::MAX as usize => {
+                self.write.push(126);
+                self.write.extend_from_slice(&(x as u16).to_be_bytes());
+            }
+            x => {
+                self.write.push(127);
+                self.write.extend_from_slice(&(x as u64).to_be_bytes());
+            }
+        }
+
+        self.write.extend_from_slice(payload);
+    }
+    /*
+     * Queue Close Frame, nothing may be sent after it
+     */
+    fn queue_close(&mut self, close: Option<(u16, String)>) {
+        let mut payload: Vec<u8> = Vec::new();
+
+        if let Some((code, reason)) = close {
+            payload.extend_from_slice(&code.to_be_bytes());
+            payload.extend_from_slice(reason.as_bytes());
+        }
+
+        self.queue(0x8, &payload);
+        self.close_sent = true;
+    }
+    /*
+     * Fail the Connection with a Close Code
+     */
+    async fn fail(&mut self, code: u16) {
+        if !self.close_sent {
+            self.queue_close(Some((code, String::new())));
+        }
+
+        let _ = self.flush().await;
+        let _ = self.stream.shutdown().await;
+        self.closed = true;
+    }
+    /*
+     * Write queued Frames, progress survives cancellation
+     */
+    async fn flush(&mut self) -> Result<(), Error> {
+        while !self.write.is_empty() {
+            let size: usize = self.stream.write(&self.write).await?;
+
+            if size == 0 {
+                return Err(Error::new(ErrorKind::WriteZero, "WebSocket closed"));
+            }
+
+            self.write.drain(..size);
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Complete Data Message, Text must be UTF-8
+ */
+fn message(opcode: u8, data: Vec<u8>) -> Result<Message, u16> {
+    if opcode == 0x2 {
+        return Ok(Message::Binary(data));
+    }
+
+    match String::from_utf8(data) {
+        Ok(x) => Ok(Message::Text(x)),
+        Err(_) => Err(1007),
+    }
+}
+
+/// Validate the Upgrade Request & prepare the Handshake
+///
+/// Used by `websocket!`, the upgrade happens after all Tails ran.
+#[doc(hidden)]
+pub async fn upgrade(mut c: Context, on_upgrade: Arc<Upgrade>) -> Returns {
+    /*
+     * HTTP/2 has no Upgrade (Extended CONNECT is not supported)
+     */
+    if c.request.http_version >= 2.0 {
+        c.response
+            .error(RequestError::new(
+                501,
+                "WebSocket over HTTP/2 is not supported",
+            ))
+            .await;
+        return (c, None);
+    }
+
+    if !c.request.headers.has_token("upgrade", "websocket")
+        || !c.request.headers.has_token("connection", "upgrade")
+    {
+        c.response.headers.insert("Upgrade", "websocket");
+        c.response
+            .error(RequestError::new(426, "WebSocket upgrade required"))
+            .await;
+        return (c, None);
+    }
+
+    if c.request.http_version < 1.1 {
+        c.response
+            .error(RequestError::new(400, "WebSocket requires HTTP/1.1"))
+            .await;
+        return (c, None);
+    }
+
+    if c.request
+        .headers
+        .get("sec-websocket-version")
+        .map(|v: &str| v.trim())
+        != Some("13")
+    {
+        c.response.headers.insert("Sec-WebSocket-Version", "13");
+        c.response
+            .error(RequestError::new(426, "Unsupported WebSocket version"))
+            .await;
+        return (c, None);
+    }
+    /*
+     * Key must be 16 random bytes, Base64 encoded
+     */
+    let key: String = c
+        .request
+        .headers
+        .get("sec-websocket-key")
+        .map(|v: &str| v.trim().to_owned())
+        .unwrap_or_default();
+
+    if STANDARD.decode(&key).ok().map(|k: Vec<u8>| k.len()) != Some(16) {
+        c.response
+            .error(RequestError::new(400, "Invalid Sec-WebSocket-Key"))
+            .await;
+        return (c, None);
+    }
+
+    let mut sha1: Sha1 = Sha1::new();
+    sha1.update(key.as_bytes());
+    sha1.update(GUID.as_bytes());
+    let accept: String = STANDARD.encode(sha1.finalize());
+
+    c.response.status = 101;
+    c.response.headers.insert("Upgrade", "websocket");
+    c.response.headers.insert("Connection", "Upgrade");
+    c.response.headers.insert("Sec-WebSocket-Accept", &accept);
+    c.response.upgrade = Some(OnUpgrade(on_upgrade));
+
+    (c, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{duplex, DuplexStream};
+
+    /*
+     * Masked Client Frame
+     */
+    fn frame(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask: [u8; 4] = [1, 2, 3, 4];
+        let mut frame: Vec<u8> = vec![(fin as u8) << 7 | opcode];
+
+        match payload.len() {
+            x if x < 126 => frame.push(0x80 | x as u8),
+            x => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(x as u16).to_be_bytes());
+            }
+        }
+
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    /*
+     * WebSocket with Frames already received, and its Client end
+     */
+    fn socket(read: Vec<u8>, max_message_size: usize) -> (WebSocket, DuplexStream) {
+        let (client, server) = duplex(1024);
+
+        (
+            WebSocket::new(Box::new(server), read, max_message_size),
+            client,
+        )
+    }
+
+    #[tokio::test]
+    async fn text() {
+        let (mut ws, _client) = socket(frame(true, 0x1, b"hello"), 1024);
+
+        assert_eq!(ws.recv().await, Some(Message::Text("hello".into())));
+    }
+
+    #[tokio::test]
+    async fn fragmented() {
+        let mut read: Vec<u8> = frame(false, 0x2, b"ab");
+        read.extend(frame(true, 0x9, b"p"));
+        read.extend(frame(true, 0x0, b"cd"));
+
+        let (mut ws, _client) = socket(read, 1024);
+
+        assert_eq!(ws.recv().await, Some(Message::Ping(b"p".to_vec())));
+        assert_eq!(ws.recv().await, Some(Message::Binary(b"abcd".to_vec())));
+    }
+
+    #[tokio::test]
+    async fn unmasked_frame_fails() {
+        let (mut ws, mut client) = socket(vec![0x81, 0x01, b'a'], 1024);
+
+        assert_eq!(ws.recv().await, None);
+        /*
+         * Close 1002 (Protocol Error)
+         */
+        let mut close: [u8; 4] = [0; 4];
+        client.read_exact(&mut close).await.unwrap();
+
+        assert_eq!(close, [0x88, 0x02, 0x03, 0xEA]);
+    }
+
+    #[tokio::test]
+    async fn message_size_limit() {
+        let (mut ws, _client) = socket(frame(true, 0x1, &[b'a'; 200]), 100);
+        assert_eq!(ws.recv().await, None);
+
+        let mut read: Vec<u8> = frame(false, 0x1, &[b'a'; 60]);
+        read.extend(frame(true, 0x0, &[b'a'; 60]));
+
+        let (mut ws, mut client) = socket(read, 100);
+        assert_eq!(ws.recv().await, None);
+        /*
+         * Close 1009 (Message Too Big)
+         */
+        let mut close: [u8; 4] = [0; 4];
+        client.read_exact(&mut close).await.unwrap();
+
+        assert_eq!(close, [0x88, 0x02, 0x03, 0xF1]);
+    }
+
+    #[tokio::test]
+    async fn control_payload_too_long() {
+        let (mut ws, _client) = socket(Vec::new(), 1024);
+
+        for message in [
+            Message::Ping(vec![0; 126]),
+            Message::Pong(vec![0; 126]),
+            Message::Close(Some((1000, "a".repeat(124)))),
+        ] {
+            let send: Error = ws.send(message).await.unwrap_err();
+            assert_eq!(send.kind(), ErrorKind::InvalidInput);
+        }
+
+        assert!(ws.send(Message::Ping(vec![0; 125])).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn close_code() {
+        let (mut ws, mut client) = socket(frame(true, 0x8, &[0x03, 0xE8, b'o', b'k']), 1024);
+
+        assert_eq!(
+            ws.recv().await,
+            Some(Message::Close(Some((1000, "ok".into()))))
+        );
+        /*
+         * Echoed without Reason
+         */
+        let mut close: [u8; 4] = [0; 4];
+        client.read_exact(&mut close).await.unwrap();
+
+        assert_eq!(close, [0x88, 0x02, 0x03, 0xE8]);
+    }
+
+    #[tokio::test]
+    async fn invalid_close_code() {
+        for code in [0u16, 999, 1004, 1005, 1006, 1015, 2000] {
+            let (mut ws, mut client) = socket(frame(true, 0x8, &code.to_be_bytes()), 1024);
+
+            assert_eq!(ws.recv().await, None);
+            /*
+             * Close 1002 (Protocol Error)
+             */
+            let mut close: [u8; 4] = [0; 4];
+            client.read_exact(&mut close).await.unwrap();
+
+            assert_eq!(close, [0x88, 0x02, 0x03, 0xEA]);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::structs::listener::Listener;
+use socket2::{SockAddr, Socket, Type};
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixListener as StdUnixListener;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use tokio::io::{Error, ErrorKind};
+use tokio::net::{TcpListener, UnixListener};
+
+/*
+ * Listener from an already bound & listening Socket (TCP or Unix)
+ */
+pub(crate) fn adopt_listener(fd: OwnedFd) -> Result<Listener, Error> {
+    let socket: Socket = Socket::from(fd);
+
+    if socket.r#type()? != Type::STREAM {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Inherited Socket is not a Stream Socket",
+        ));
+    }
+
+    socket.set_nonblocking(true)?;
+
+    let address: SockAddr = socket.local_addr()?;
+
+    if address.as_socket().is_some() {
+        let listener: std::net::TcpListener = OwnedFd::from(socket).into();
+
+        return Ok(Listener::Tcp(TcpListener::from_std(listener)?));
+    }
+
+    if address.is_unix() {
+        let path: PathBuf = address
+            .as_pathname()
+            .map(|x| x.to_owned())
+            .unwrap_or_default();
+
+        let listener: StdUnixListener = OwnedFd::from(socket).into();
+        /*
+         * Socket File belongs to whoever created it, it is kept on stop
+         */
+        return Ok(Listener::Unix {
+            listener: UnixListener::from_std(listener)?,
+            path,
+            cleanup: AtomicBool::new(false),
+        });
+    }
+
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        "Inherited Socket is neither TCP nor Unix",
+    ))
+}
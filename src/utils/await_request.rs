@@ -0,0 +1,30 @@
+use crate::structs::definition::IoReader;
+use crate::structs::shutdown::Shutdown;
+use tokio::io::AsyncReadExt;
+use tokio::select;
+
+/*
+ * Wait for the first bytes of the next Request on an idle Connection
+ * Returns false when the Connection is closed or the Server shuts down
+ */
+pub(crate) async fn await_request(
+    reader: &mut IoReader,
+    buffer: &mut Vec<u8>,
+    shutdown: &mut Shutdown,
+) -> bool {
+    let mut chunk: [u8; 4096] = [0; 4096];
+    /*
+     * Reading is cancel safe, nothing is lost if Shutdown wins
+     */
+    select! {
+        biased;
+        _ = shutdown.wait() => false,
+        read = reader.read(&mut chunk) => match read {
+            Ok(0) | Err(_) => false,
+            Ok(x) => {
+                buffer.extend_from_slice(&chunk[..x]);
+                true
+            }
+        },
+    }
+}
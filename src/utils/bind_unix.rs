@@ -0,0 +1,42 @@
+use std::fs::Permissions;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::Path;
+use tokio::fs::{remove_file, set_permissions, symlink_metadata};
+use tokio::io::{Error, ErrorKind};
+use tokio::net::{UnixListener, UnixStream};
+
+/*
+ * Bind Unix Socket, replacing a stale Socket File of a previous Run
+ */
+pub(crate) async fn bind_unix(path: &Path, mode: Option<u32>) -> Result<UnixListener, Error> {
+    if let Ok(metadata) = symlink_metadata(path).await {
+        if !metadata.file_type().is_socket() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists and is not a Socket", path.display()),
+            ));
+        }
+        /*
+         * Still accepting: another Server owns it
+         */
+        if UnixStream::connect(path).await.is_ok() {
+            return Err(Error::new(
+                ErrorKind::AddrInUse,
+                format!("{} is in use", path.display()),
+            ));
+        }
+
+        remove_file(path).await?;
+    }
+
+    let listener: UnixListener = UnixListener::bind(path)?;
+
+    if let Some(mode) = mode {
+        if let Err(e) = set_permissions(path, Permissions::from_mode(mode)).await {
+            let _ = remove_file(path).await;
+            return Err(e);
+        }
+    }
+
+    Ok(listener)
+}
@@ -0,0 +1,80 @@
+use crate::structs::config::Config;
+use crate::structs::definition::ReadError;
+use crate::structs::request::Request;
+use crate::utils::multipart_parser::MultipartParser;
+use crate::utils::parse_mime::parse_mime;
+
+/*
+ * Destination of decoded Body bytes
+ * Plain Bodies are buffered, multipart/form-data is parsed while reading
+ */
+pub(crate) enum BodySink {
+    Buffer { body: Vec<u8>, max: usize },
+    Multipart(Box<MultipartParser>),
+}
+
+impl BodySink {
+    pub(crate) async fn new(request: &mut Request, config: &Config) -> Result<BodySink, ReadError> {
+        let content_type: String = request.header("content-type").await.unwrap_or_default();
+
+        let mime: String = parse_mime(&content_type).await;
+
+        if mime != "multipart/form-data" {
+            return Ok(BodySink::Buffer {
+                body: Vec::new(),
+                max: config.max_body_size,
+            });
+        }
+
+        let boundary: Option<String> = content_type.split(';').skip(1).find_map(|p: &str| {
+            let (k, v) = p.split_once('=')?;
+            match k.trim().eq_ignore_ascii_case("boundary") {
+                true => Some(v.trim().trim_matches('"').to_owned()),
+                false => None,
+            }
+        });
+
+        match boundary {
+            Some(b) if !b.is_empty() && b.len() <= 70 => Ok(BodySink::Multipart(Box::new(
+                MultipartParser::new(&b, config),
+            ))),
+            _ => Err(ReadError::Status(400)),
+        }
+    }
+    /*
+     * Largest Body this Sink accepts
+     */
+    pub(crate) fn limit(&self, config: &Config) -> usize {
+        match self {
+            BodySink::Buffer { max, .. } => *max,
+            BodySink::Multipart(_) => config.max_upload_size,
+        }
+    }
+
+    pub(crate) async fn write(&mut self, data: &[u8]) -> Result<(), ReadError> {
+        match self {
+            BodySink::Buffer { body, max } => {
+                if data.len() > max.saturating_sub(body.len()) {
+                    return Err(ReadError::Status(413));
+                }
+                body.extend_from_slice(data);
+                Ok(())
+            }
+            BodySink::Multipart(parser) => parser.write(data).await,
+        }
+    }
+
+    pub(crate) async fn finish(&mut self, request: &mut Request) -> Result<(), ReadError> {
+        match self {
+            BodySink::Buffer { body, .. } => request.body = std::mem::take(body),
+            BodySink::Multipart(parser) => request.parts = parser.finish().await?,
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn abort(&mut self) {
+        if let BodySink::Multipart(parser) = self {
+            parser.abort().await;
+        }
+    }
+}
@@ -0,0 +1,512 @@
+use serde::de::value::{SeqDeserializer, StringDeserializer};
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, Deserializer, Error, IntoDeserializer, MapAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::vec::IntoIter;
+
+/*
+ * Deserialize Key Value pairs (Query, Form, Params) into a Struct / Map
+ * Keys match case-insensitively (like Request::query_all), repeated keys
+ * fill sequences, single values take the first occurrence
+ */
+pub(crate) async fn deserialize_pairs<T: DeserializeOwned>(
+    pairs: &[(String, String)],
+) -> Result<T, PairsError> {
+    let mut grouped: Vec<(String, Vec<String>)> = Vec::new();
+
+    for (k, v) in pairs {
+        match grouped
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(k))
+        {
+            Some((_, values)) => values.push(v.to_owned()),
+            None => grouped.push((k.to_owned(), vec![v.to_owned()])),
+        }
+    }
+
+    T::deserialize(Pairs {
+        iter: grouped.into_iter(),
+        value: None,
+    })
+}
+
+/*
+ * Error with the failing field name
+ */
+#[derive(Debug)]
+pub(crate) struct PairsError(pub(crate) String);
+
+impl Display for PairsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PairsError {}
+
+impl Error for PairsError {
+    fn custom<T: Display>(msg: T) -> PairsError {
+        PairsError(msg.to_string())
+    }
+}
+
+/*
+ * Top Level: Map of Keys
+ */
+struct Pairs {
+    iter: IntoIter<(String, Vec<String>)>,
+    value: Option<(String, Vec<String>)>,
+}
+
+impl<'de> Deserializer<'de> for Pairs {
+    type Error = PairsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_map(self)
+    }
+    /*
+     * Keys take the spelling of the Field they match
+     */
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        let grouped: Vec<(String, Vec<String>)> = self
+            .iter
+            .map(
+                |(k, values)| match fields.iter().find(|f: &&&str| f.eq_ignore_ascii_case(&k)) {
+                    Some(field) => (field.to_string(), values),
+                    None => (k, values),
+                },
+            )
+            .collect();
+
+        visitor.visit_map(Pairs {
+            iter: grouped.into_iter(),
+            value: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for Pairs {
+    type Error = PairsError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, PairsError> {
+        match self.iter.next() {
+            Some((k, values)) => {
+                self.value = Some((k.clone(), values));
+                let key: StringDeserializer<PairsError> = k.into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, PairsError> {
+        let (key, values) = self
+            .value
+            .take()
+            .ok_or_else(|| PairsError::custom("value requested before key"))?;
+
+        seed.deserialize(Values { values })
+            .map_err(|e: PairsError| PairsError(format!("field `{}`: {}", key, e)))
+    }
+}
+
+/*
+ * All Values of one Key
+ */
+struct Values {
+    values: Vec<String>,
+}
+
+impl Values {
+    fn first(mut self) -> Part {
+        Part(self.values.swap_remove(0))
+    }
+}
+
+impl<'de> Deserializer<'de> for Values {
+    type Error = PairsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_any(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        let parts = self.values.into_iter().map(Part);
+        let mut seq: SeqDeserializer<_, PairsError> = SeqDeserializer::new(parts);
+        let value: V::Value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.deserialize_seq(visitor)
+    }
+
+    /*
+     * A single empty Value is None, otherwise all Values are kept
+     * (Option<Vec<T>> gets every Value)
+     */
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        match self.values.as_slice() {
+            [x] if x.is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_bool(visitor)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_i8(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_i16(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_i32(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_i64(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_u8(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_u16(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_u32(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_u64(visitor)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_f32(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_f64(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_char(visitor)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.first().deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.first().deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit_struct tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/*
+ * Single Value
+ */
+struct Part(String);
+
+impl Part {
+    fn parse<T: FromStr>(self) -> Result<T, PairsError>
+    where
+        T::Err: Display,
+    {
+        self.0
+            .trim()
+            .parse()
+            .map_err(|e: T::Err| PairsError(format!("invalid value `{}`: {}", self.0, e)))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, PairsError> for Part {
+    type Deserializer = Part;
+
+    fn into_deserializer(self) -> Part {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Part {
+    type Error = PairsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        match self.0.trim().to_lowercase().as_str() {
+            "true" | "on" | "1" => visitor.visit_bool(true),
+            "false" | "off" | "0" => visitor.visit_bool(false),
+            _ => Err(PairsError(format!(
+                "invalid value `{}`: expected a boolean",
+                self.0
+            ))),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_i8(self.parse()?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_i16(self.parse()?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_i32(self.parse()?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_i64(self.parse()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_u8(self.parse()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_u16(self.parse()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_u32(self.parse()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_u64(self.parse()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_f32(self.parse()?)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_f64(self.parse()?)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_char(self.parse()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        let variant: StringDeserializer<PairsError> = self.0.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    fn pairs(x: &[(&str, &str)]) -> Vec<(String, String)> {
+        x.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Sort {
+        Asc,
+        Desc,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Query {
+        page: u32,
+        active: bool,
+        #[serde(default)]
+        tag: Vec<String>,
+        name: Option<String>,
+        sort: Option<Sort>,
+        ids: Option<Vec<u8>>,
+    }
+
+    #[tokio::test]
+    async fn scalars() {
+        let query: Query = deserialize_pairs(&pairs(&[("page", " 2 "), ("active", "on")]))
+            .await
+            .unwrap();
+
+        assert_eq!(query.page, 2);
+        assert!(query.active);
+        assert!(query.tag.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sequence() {
+        let query: Query = deserialize_pairs(&pairs(&[
+            ("page", "1"),
+            ("active", "0"),
+            ("tag", "a"),
+            ("tag", "b"),
+        ]))
+        .await
+        .unwrap();
+
+        assert_eq!(query.tag, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn keys_ignore_case() {
+        let query: Query = deserialize_pairs(&pairs(&[
+            ("Page", "1"),
+            ("ACTIVE", "true"),
+            ("Tag", "a"),
+            ("tag", "b"),
+        ]))
+        .await
+        .unwrap();
+
+        assert_eq!(query.page, 1);
+        assert_eq!(query.tag, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn option() {
+        let query: Query = deserialize_pairs(&pairs(&[
+            ("page", "1"),
+            ("active", "1"),
+            ("name", ""),
+            ("ids", "3"),
+            ("ids", "4"),
+        ]))
+        .await
+        .unwrap();
+
+        assert_eq!(query.name, None);
+        assert_eq!(query.sort, None);
+        assert_eq!(query.ids, Some(vec![3, 4]));
+
+        let query: Query =
+            deserialize_pairs(&pairs(&[("page", "1"), ("active", "1"), ("name", "x")]))
+                .await
+                .unwrap();
+
+        assert_eq!(query.name, Some("x".to_owned()));
+        assert_eq!(query.ids, None);
+    }
+
+    #[tokio::test]
+    async fn enumeration() {
+        let query: Query =
+            deserialize_pairs(&pairs(&[("page", "1"), ("active", "1"), ("sort", "desc")]))
+                .await
+                .unwrap();
+
+        assert_eq!(query.sort, Some(Sort::Desc));
+
+        let query: Result<Query, PairsError> =
+            deserialize_pairs(&pairs(&[("page", "1"), ("active", "1"), ("sort", "up")])).await;
+
+        assert!(query.unwrap_err().0.starts_with("field `sort`"));
+    }
+
+    #[tokio::test]
+    async fn missing_field() {
+        let query: Result<Query, PairsError> = deserialize_pairs(&pairs(&[("active", "1")])).await;
+
+        assert_eq!(query.unwrap_err().0, "missing field `page`");
+    }
+
+    #[tokio::test]
+    async fn parse_error() {
+        let query: Result<Query, PairsError> =
+            deserialize_pairs(&pairs(&[("page", "two"), ("active", "1")])).await;
+
+        assert_eq!(
+            query.unwrap_err().0,
+            "field `page`: invalid value `two`: invalid digit found in string"
+        );
+
+        let query: Result<Query, PairsError> =
+            deserialize_pairs(&pairs(&[("page", "1"), ("active", "maybe")])).await;
+
+        assert_eq!(
+            query.unwrap_err().0,
+            "field `active`: invalid value `maybe`: expected a boolean"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::server::Server;
+use crate::structs::context::Context;
+use crate::structs::definition::{Callback, Returns, Tail};
+use crate::utils::find_callback::{find_callback, IsFind};
+use crate::utils::parse_segments::parse_segments;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/*
+ * Run Middlewares, Routes & Tails for one Request
+ */
+pub(crate) async fn dispatch(server: &Server, mut context: Context) -> Context {
+    server.stats.requests.fetch_add(1, Ordering::Relaxed);
+    /*
+     * Find & Callback
+     */
+    let mut tails: Vec<Tail> = Vec::new();
+
+    let method: String = context.request.method.clone();
+    let path: String = context.request.path.clone();
+
+    let segments: Vec<String> = parse_segments(&path).await;
+
+    for add in server.adds.iter() {
+        if !context.next {
+            break;
+        }
+
+        let method_cp: String = if add.0 == "*" {
+            method.clone().to_lowercase()
+        } else {
+            add.0.to_lowercase()
+        };
+
+        let path_cp: String = if add.1 == "*" {
+            path.clone().to_lowercase()
+        } else {
+            add.1.to_lowercase()
+        };
+
+        let callback_cp: Vec<Arc<Callback>> = add.2.to_owned();
+
+        if method_cp != method.to_lowercase() {
+            continue;
+        }
+        /*
+         * Static
+         */
+        if path_cp == path.to_lowercase() {
+            for callback in callback_cp.clone() {
+                context.next = false;
+
+                context.request.param_store = Vec::new();
+
+                let callback_returns: Returns = (callback)(context).await;
+
+                context = callback_returns.0;
+
+                if let Some(x) = callback_returns.1 {
+                    tails.push(x);
+                }
+
+                if !context.next {
+                    break;
+                }
+            }
+
+            continue;
+        }
+        /*
+         * Dynamic
+         */
+        let find_callback: IsFind = find_callback(&segments, add.1.to_owned()).await;
+
+        if find_callback.find {
+            for callback in callback_cp.clone() {
+                context.next = false;
+
+                context.request.param_store = find_callback.param.to_owned();
+
+                let callback_returns: Returns = (callback)(context).await;
+
+                context = callback_returns.0;
+
+                if let Some(x) = callback_returns.1 {
+                    tails.push(x);
+                }
+
+                if !context.next {
+                    break;
+                }
+            }
+        }
+    }
+    /*
+     * Route Not Found
+     */
+    if context.next {
+        /*
+         * Default Status & Body
+         */
+        context.response.status = 404;
+        context.response.body = "Not Found".into();
+    }
+    /*
+     * Tail
+     */
+    if !tails.is_empty() {
+        for i in tails.iter().rev() {
+            context = (i)(context).await;
+            if !context.next {
+                break;
+            }
+        }
+    }
+
+    context
+}
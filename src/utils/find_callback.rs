@@ -6,15 +6,7 @@ pub(crate) struct IsFind {
     pub(crate) param: Vec<(String, String)>,
 }
 
-pub(crate) async fn find_callback(path: String, callback_path: String) -> IsFind {
-    /*
-     * Path Split
-     */
-    let path_split: Vec<String> = path
-        .split('/')
-        .filter(|x: &&str| !x.is_empty())
-        .map(|x: &str| x.to_owned())
-        .collect();
+pub(crate) async fn find_callback(path_split: &[String], callback_path: String) -> IsFind {
     /*
      * Callback Path Split
      * Dynamic Match
@@ -48,7 +40,7 @@ pub(crate) async fn find_callback(path: String, callback_path: String) -> IsFind
             /*
              * Static
              */
-            if callback_path_elm == path_split[i] {
+            if callback_path_elm.eq_ignore_ascii_case(&path_split[i]) {
                 prepare_path.push_str(&format!("/{}", callback_path_elm));
             }
             /*
@@ -0,0 +1,197 @@
+use crate::structs::config::Config;
+use crate::structs::definition::{IoReader, IoWriter, ReadError};
+use crate::structs::headers::Headers;
+use crate::structs::request::Request;
+use crate::utils::body_sink::BodySink;
+use crate::utils::get_body_chunked::get_body_chunked;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, Error};
+use tokio::time::timeout;
+
+/*
+ * Read Body by Content-Length or Transfer-Encoding: chunked
+ * Bytes after the Body (pipelined Requests) stay in the Buffer
+ */
+pub(crate) async fn get_body(
+    reader: &mut IoReader,
+    writer: &mut IoWriter,
+    request: &mut Request,
+    buffer: &mut Vec<u8>,
+    config: &Config,
+) -> Result<(), ReadError> {
+    let transfer_encoding: Option<String> = request.header("transfer-encoding").await;
+    let content_length: Option<String> = request.header("content-length").await;
+
+    if transfer_encoding.is_none() && content_length.is_none() {
+        return Ok(());
+    }
+    /*
+     * Both framings present: possible Request Smuggling
+     */
+    if transfer_encoding.is_some() && content_length.is_some() {
+        return Err(ReadError::Status(400));
+    }
+
+    let mut sink: BodySink = BodySink::new(request, config).await?;
+
+    /*
+     * Slow Clients must not hold the Connection
+     */
+    let body_read: Result<(), ReadError> = timeout(config.body_timeout, async {
+        match (transfer_encoding, content_length) {
+            (Some(te), _) => {
+                let codings: Vec<String> = te
+                    .split(',')
+                    .map(|x: &str| x.trim().to_lowercase())
+                    .filter(|x: &String| !x.is_empty())
+                    .collect();
+
+                if codings.last().map(|x: &String| x.as_str()) != Some("chunked") {
+                    return Err(ReadError::Status(400));
+                }
+
+                if codings.len() > 1 {
+                    return Err(ReadError::Status(501));
+                }
+
+                continue_100(writer, request).await?;
+
+                get_body_chunked(reader, buffer, &mut sink, config)
+                    .await
+                    .map(|trailers: Headers| request.trailers = trailers)
+            }
+            (None, Some(cl)) => {
+                /*
+                 * Repeated Content-Length is only accepted with identical values
+                 */
+                let lengths: Vec<&str> = cl.split(',').map(|x: &str| x.trim()).collect();
+
+                if lengths.iter().any(|x: &&str| *x != lengths[0]) {
+                    return Err(ReadError::Status(400));
+                }
+
+                let length: usize = parse_content_length(lengths[0])?;
+
+                if length > sink.limit(config) {
+                    return Err(ReadError::Status(413));
+                }
+
+                if length > buffer.len() {
+                    continue_100(writer, request).await?;
+                }
+
+                get_body_length(reader, buffer, length, &mut sink).await
+            }
+            (None, None) => Ok(()),
+        }
+    })
+    .await
+    .unwrap_or(Err(ReadError::Status(408)));
+
+    let body_finish: Result<(), ReadError> = match body_read {
+        Ok(_) => sink.finish(request).await,
+        Err(e) => Err(e),
+    };
+    /*
+     * Remove partial Uploads
+     */
+    if body_finish.is_err() {
+        sink.abort().await;
+    }
+
+    body_finish
+}
+
+/*
+ * Content-Length = 1*DIGIT, no Sign or Whitespace
+ */
+fn parse_content_length(value: &str) -> Result<usize, ReadError> {
+    if value.is_empty() || !value.bytes().all(|b: u8| b.is_ascii_digit()) {
+        return Err(ReadError::Status(400));
+    }
+
+    value.parse().map_err(|_| ReadError::Status(400))
+}
+
+/*
+ * Read Body until Content-Length bytes are consumed
+ */
+async fn get_body_length(
+    reader: &mut IoReader,
+    buffer: &mut Vec<u8>,
+    length: usize,
+    sink: &mut BodySink,
+) -> Result<(), ReadError> {
+    let available: usize = length.min(buffer.len());
+
+    sink.write(&buffer[..available]).await?;
+    buffer.drain(..available);
+
+    let mut remaining: usize = length - available;
+
+    let mut chunk: [u8; 8192] = [0; 8192];
+
+    while remaining > 0 {
+        let max: usize = remaining.min(chunk.len());
+
+        let buffer_reader: Result<usize, Error> = reader.read(&mut chunk[..max]).await;
+
+        let size: usize = match buffer_reader {
+            Ok(x) => x,
+            Err(e) => {
+                println!("[Error] Error in Stream Body Reader:\n{}", e);
+                return Err(ReadError::Close);
+            }
+        };
+        /*
+         * Connection closed before the whole Body arrived
+         */
+        if size == 0 {
+            return Err(ReadError::Close);
+        }
+
+        sink.write(&chunk[..size]).await?;
+        remaining -= size;
+    }
+
+    Ok(())
+}
+
+/*
+ * Client waits for approval before sending the Body
+ */
+async fn continue_100(writer: &mut IoWriter, request: &mut Request) -> Result<(), ReadError> {
+    let expect: Option<String> = request.header("expect").await;
+
+    if expect.map(|e: String| e.eq_ignore_ascii_case("100-continue")) != Some(true) {
+        return Ok(());
+    }
+
+    let stream_write: Result<(), Error> = writer.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").await;
+
+    if stream_write.is_err() {
+        return Err(ReadError::Close);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_length() {
+        assert!(matches!(parse_content_length("42"), Ok(42)));
+        assert!(matches!(parse_content_length("0"), Ok(0)));
+    }
+
+    #[test]
+    fn content_length_digits_only() {
+        for value in ["+3", "-3", "", " 3", "3 ", "0x3", "3.0"] {
+            assert!(matches!(
+                parse_content_length(value),
+                Err(ReadError::Status(400))
+            ));
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::structs::config::Config;
+use crate::structs::definition::{IoReader, ReadError};
+use crate::structs::headers::Headers;
+use crate::utils::body_sink::BodySink;
+use crate::utils::parse_headers::parse_headers;
+use tokio::io::AsyncReadExt;
+
+/*
+ * Longest accepted Chunk Size / Trailer line
+ * (all Trailer lines together are limited like the Header)
+ */
+const MAX_LINE: usize = 8 * 1024;
+
+/*
+ * Decode Transfer-Encoding: chunked Body into the Sink
+ * Returns the Trailer fields
+ */
+pub(crate) async fn get_body_chunked(
+    reader: &mut IoReader,
+    buffer: &mut Vec<u8>,
+    sink: &mut BodySink,
+    config: &Config,
+) -> Result<Headers, ReadError> {
+    loop {
+        /*
+         * Chunk Size [; Chunk Extensions]
+         */
+        let line: String = read_line(reader, buffer).await?;
+
+        let size: &str = line.split(';').next().unwrap_or_default().trim();
+
+        let size: usize = match usize::from_str_radix(size, 16) {
+            Ok(x) if !size.is_empty() && !size.starts_with('+') => x,
+            _ => return Err(ReadError::Status(400)),
+        };
+
+        if size == 0 {
+            break;
+        }
+        /*
+         * Chunk Data
+         */
+        let mut remaining: usize = size;
+
+        let available: usize = remaining.min(buffer.len());
+        sink.write(&buffer[..available]).await?;
+        buffer.drain(..available);
+        remaining -= available;
+
+        let mut chunk: [u8; 8192] = [0; 8192];
+
+        while remaining > 0 {
+            let max: usize = remaining.min(chunk.len());
+
+            let read: usize = match reader.read(&mut chunk[..max]).await {
+                Ok(0) | Err(_) => return Err(ReadError::Close),
+                Ok(x) => x,
+            };
+
+            sink.write(&chunk[..read]).await?;
+            remaining -= read;
+        }
+
+        if !read_line(reader, buffer).await?.is_empty() {
+            return Err(ReadError::Status(400));
+        }
+    }
+    /*
+     * Trailer Fields
+     */
+    let mut lines: Vec<String> = Vec::new();
+    let mut size: usize = 0;
+
+    loop {
+        let line: String = read_line(reader, buffer).await?;
+
+        if line.is_empty() {
+            break;
+        }
+
+        size += line.len() + 2;
+
+        if size > config.max_header_size || lines.len() >= config.max_header_count {
+            return Err(ReadError::Status(431));
+        }
+
+        lines.push(line);
+    }
+
+    let trailers: Headers = parse_headers(
+        lines.iter().map(|x: &String| x.as_str()),
+        config.max_header_count,
+    )
+    .await?;
+
+    Ok(trailers)
+}
+
+/*
+ * Read one CRLF terminated line from Buffer, filling it from the Reader
+ */
+async fn read_line(reader: &mut IoReader, buffer: &mut Vec<u8>) -> Result<String, ReadError> {
+    let mut searched: usize = 0;
+
+    loop {
+        if let Some(i) = buffer[searched..]
+            .windows(2)
+            .position(|w: &[u8]| w == b"\r\n")
+        {
+            let end: usize = searched + i;
+            let line: String = String::from_utf8_lossy(&buffer[..end]).to_string();
+            buffer.drain(..end + 2);
+            return Ok(line);
+        }
+
+        if buffer.len() > MAX_LINE {
+            return Err(ReadError::Status(400));
+        }
+
+        searched = buffer.len().saturating_sub(1);
+
+        let mut chunk: [u8; 4096] = [0; 4096];
+
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err(ReadError::Close),
+            Ok(x) => buffer.extend_from_slice(&chunk[..x]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::definition::IoStream;
+    use tokio::io::{duplex, split};
+
+    /*
+     * Decode a complete chunked Body (already in the Buffer)
+     */
+    async fn decode(body: String, config: &Config) -> Result<Headers, ReadError> {
+        let (_, stream) = duplex(64);
+        let stream: IoStream = Box::new(stream);
+        let (mut reader, _) = split(stream);
+
+        let mut buffer: Vec<u8> = body.into_bytes();
+        let mut sink: BodySink = BodySink::Buffer {
+            body: Vec::new(),
+            max: config.max_body_size,
+        };
+
+        get_body_chunked(&mut reader, &mut buffer, &mut sink, config).await
+    }
+
+    #[tokio::test]
+    async fn trailers() {
+        let trailers: Headers = decode(
+            "3\r\nabc\r\n0\r\nX-Sum: 1\r\n\r\n".into(),
+            &Config::default(),
+        )
+        .await
+        .ok()
+        .unwrap();
+
+        assert_eq!(trailers.get("x-sum"), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn trailer_count_limit() {
+        let config: Config = Config::default();
+
+        let lines: String = (0..=config.max_header_count)
+            .map(|i: usize| format!("X-{}: 1\r\n", i))
+            .collect();
+
+        let decoded = decode(format!("0\r\n{}\r\n", lines), &config).await;
+
+        assert!(matches!(decoded, Err(ReadError::Status(431))));
+    }
+
+    #[tokio::test]
+    async fn trailer_size_limit() {
+        let config: Config = Config::default();
+
+        let lines: String = (0..config.max_header_size / 1024 + 1)
+            .map(|i: usize| format!("X-{}: {}\r\n", i, "a".repeat(1024)))
+            .collect();
+
+        let decoded = decode(format!("0\r\n{}\r\n", lines), &config).await;
+
+        assert!(matches!(decoded, Err(ReadError::Status(431))));
+    }
+}
@@ -1,19 +1,88 @@
+use crate::structs::config::Config;
+use crate::structs::definition::{IoReader, ReadError};
+use crate::structs::headers::Headers;
+use crate::utils::parse_headers::parse_headers;
+use std::mem::replace;
 use tokio::io::{AsyncReadExt, Error};
-use tokio::net::tcp::OwnedReadHalf;
 
-pub(crate) async fn get_header(mut reader: OwnedReadHalf) -> String {
-    let mut header: [u8; 512] = [0; 512];
+/*
+ * Read Header until the blank line
+ * Bytes after it (Body, pipelined Requests) stay in the Buffer
+ */
+pub(crate) async fn get_header(
+    reader: &mut IoReader,
+    buffer: &mut Vec<u8>,
+    config: &Config,
+) -> Result<(String, Headers), ReadError> {
+    let mut chunk: [u8; 4096] = [0; 4096];
+    /*
+     * Pipelined Request may be complete already
+     */
+    let mut start: usize = 0;
 
-    let buffer_reader: Result<usize, Error> = reader.read(&mut header).await;
+    let end: usize = loop {
+        if let Some(i) = buffer[start..]
+            .windows(4)
+            .position(|w: &[u8]| w == b"\r\n\r\n")
+        {
+            break start + i + 4;
+        }
 
-    if buffer_reader.is_err() {
-        println!(
-            "[Error] Error in Stream Buffer Reader:\n{}",
-            buffer_reader.err().unwrap()
-        );
+        if buffer.len() > config.max_header_size {
+            return Err(ReadError::Status(431));
+        }
 
-        return String::new();
+        let buffer_reader: Result<usize, Error> = reader.read(&mut chunk).await;
+
+        let size: usize = match buffer_reader {
+            Ok(0) => return Err(ReadError::Close),
+            Ok(x) => x,
+            Err(e) => {
+                println!("[Error] Error in Stream Buffer Reader:\n{}", e);
+                return Err(ReadError::Close);
+            }
+        };
+        /*
+         * Header Terminator (may be split across reads)
+         */
+        start = buffer.len().saturating_sub(3);
+        buffer.extend_from_slice(&chunk[..size]);
+    };
+
+    if end > config.max_header_size {
+        return Err(ReadError::Status(431));
+    }
+
+    let rest: Vec<u8> = buffer.split_off(end);
+    let head: Vec<u8> = replace(buffer, rest);
+    /*
+     * Empty lines before the Request Line are ignored
+     */
+    let skip: usize = head
+        .iter()
+        .position(|b: &u8| *b != b'\r' && *b != b'\n')
+        .unwrap_or(head.len());
+
+    let header: String = String::from_utf8_lossy(&head[skip..]).to_string();
+
+    let mut lines = header.lines();
+    /*
+     * Request Line: Method Target Version
+     */
+    let request_line: &str = lines.next().unwrap_or_default();
+    let request_line_split: Vec<&str> = request_line.split(' ').collect();
+
+    if request_line_split.len() != 3
+        || request_line_split.iter().any(|x: &&str| x.is_empty())
+        || !request_line.is_ascii()
+        || !request_line_split[2].starts_with("HTTP/")
+    {
+        return Err(ReadError::Status(400));
     }
+    /*
+     * Header Fields
+     */
+    let headers: Headers = parse_headers(lines, config.max_header_count).await?;
 
-    String::from_utf8_lossy(&header[..]).to_string()
+    Ok((header, headers))
 }
@@ -0,0 +1,93 @@
+use crate::structs::listener::Listener;
+#[cfg(unix)]
+use crate::utils::listen_fds::{LISTEN_FDS_START, LISTEN_SOCKETS};
+#[cfg(unix)]
+use crate::utils::socket_id::socket_id;
+#[cfg(unix)]
+use std::os::fd::{AsFd, AsRawFd, FromRawFd, OwnedFd, RawFd};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command};
+use std::sync::Arc;
+use tokio::io::Error;
+#[cfg(not(unix))]
+use tokio::io::ErrorKind;
+
+/*
+ * Start the Command with the Listeners as Descriptors 3, 4, ..., LISTEN_FDS
+ * & their Identities set, so `listen_fds` of the new Process takes them over
+ */
+#[cfg(unix)]
+pub(crate) fn hand_over(listeners: &[Arc<Listener>], mut command: Command) -> Result<Child, Error> {
+    let count: RawFd = listeners.len() as RawFd;
+    /*
+     * Copies above the Target Range, so moving one in place never overwrites
+     * another (close-on-exec, only the moved ones reach the new Program)
+     */
+    let mut copies: Vec<OwnedFd> = Vec::new();
+
+    for listener in listeners {
+        // SAFETY: F_DUPFD_CLOEXEC on an open Descriptor, no Memory involved
+        let fd: RawFd = unsafe {
+            libc::fcntl(
+                listener.as_fd().as_raw_fd(),
+                libc::F_DUPFD_CLOEXEC,
+                LISTEN_FDS_START + count,
+            )
+        };
+
+        if fd < 0 {
+            return Err(Error::last_os_error());
+        }
+        // SAFETY: fcntl just returned this new Descriptor, nothing else owns it
+        copies.push(unsafe { OwnedFd::from_raw_fd(fd) });
+    }
+
+    let fds: Vec<RawFd> = copies.iter().map(|fd: &OwnedFd| fd.as_raw_fd()).collect();
+
+    let sockets: Vec<String> = copies
+        .iter()
+        .map(|fd: &OwnedFd| socket_id(fd.as_fd()))
+        .collect::<Result<Vec<String>, Error>>()?;
+    /*
+     * No LISTEN_PID, the new Process' pid is unknown until it is started
+     */
+    command
+        .env("LISTEN_FDS", count.to_string())
+        .env(LISTEN_SOCKETS, sockets.join(","))
+        .env_remove("LISTEN_PID")
+        .env_remove("LISTEN_FDNAMES");
+    // SAFETY: the Closure only calls dup2 (async-signal-safe) & allocates
+    // nothing, the Copies stay open until `spawn` returns
+    unsafe {
+        command.pre_exec(move || {
+            for (i, fd) in fds.iter().enumerate() {
+                /*
+                 * dup2 clears close-on-exec on the Target
+                 */
+                if libc::dup2(*fd, LISTEN_FDS_START + i as RawFd) < 0 {
+                    return Err(Error::last_os_error());
+                }
+            }
+
+            Ok(())
+        });
+    }
+
+    let child: Child = command.spawn()?;
+
+    drop(copies);
+
+    Ok(child)
+}
+
+/*
+ * Descriptors cannot be passed on this Platform
+ */
+#[cfg(not(unix))]
+pub(crate) fn hand_over(_: &[Arc<Listener>], _: Command) -> Result<Child, Error> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "Listener Handover needs Unix",
+    ))
+}
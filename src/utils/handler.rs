@@ -1,164 +1,311 @@
 use crate::server::Server;
 use crate::structs::context::Context;
-use crate::structs::definition::{Callback, Returns, Tail};
-use crate::structs::request::Request;
-use crate::structs::response::Response;
-use crate::utils::find_callback::{find_callback, IsFind};
+use crate::structs::definition::{IoStream, OnUpgrade, ReadError};
+use crate::structs::headers::Headers;
+use crate::structs::part::Part;
+use crate::structs::shutdown::Shutdown;
+use crate::structs::tls::TlsInfo;
+use crate::utils::await_request::await_request;
+use crate::utils::dispatch::dispatch;
+use crate::utils::get_body::get_body;
 use crate::utils::get_header::get_header;
-use crate::utils::parse_http_version::parse_http_version;
-use crate::utils::parse_method::parse_method;
-use crate::utils::parse_path::parse_path;
+use crate::utils::http2::http2;
+use crate::utils::http2_upgrade::{http2_upgrade, is_http2_upgrade};
+use crate::utils::new_context::new_context;
+use crate::utils::read_preface::read_preface;
 use crate::utils::response_payload::response_payload;
 use crate::utils::response_payload_empty::response_payload_empty;
-use std::net::SocketAddr;
-use std::sync::Arc;
-use tokio::join;
-use tokio::net::TcpStream;
+use crate::utils::response_payload_error::response_payload_error;
+use crate::utils::response_upgrade::response_upgrade;
+use crate::utils::rewind::Rewind;
+use std::path::PathBuf;
+use tokio::fs::remove_file;
+use tokio::io::split;
+use tokio::time::error::Elapsed;
+use tokio::time::timeout;
 
 /*
  * Handler
+ * Serves Requests of one Connection in order until it is closed
  */
-pub(crate) async fn handler(server: Server, address: SocketAddr, stream: TcpStream) {
-    let (reader, writer) = stream.into_split();
-
-    let header: String = get_header(reader).await;
-
-    if header.is_empty() {
-        response_payload_empty(writer).await;
-        return;
-    }
-    /*
-     * Incoming
-     */
-    let ((url, path, query), method, http_version) = join!(
-        parse_path(header.clone()),
-        parse_method(header.clone()),
-        parse_http_version(header.clone())
-    );
+pub(crate) async fn handler(
+    server: Server,
+    address: String,
+    mut stream: IoStream,
+    tls: Option<TlsInfo>,
+    mut shutdown: Shutdown,
+) {
     /*
-     * Context
+     * Received bytes not consumed yet (pipelined Requests)
      */
-    let mut context: Context = Context {
-        next: true,
-        state: Vec::new(),
-        request: Request {
-            address: address.to_string(),
-            header,
-            header_store: Vec::new(),
-            param_store: Vec::new(),
-            query_store: Vec::new(),
-            method: method.clone(),
-            url,
-            path: path.clone(),
-            query,
-            http_version,
-        },
-        response: Response {
-            header: Vec::new(),
-            body: String::new(),
-            status: 200,
-            content_type: "text/html".to_owned(),
-        },
-    };
+    let mut buffer: Vec<u8> = Vec::new();
     /*
-     * Find & Callback
+     * HTTP/2 with Prior Knowledge starts with the Connection Preface
      */
-    let mut tails: Vec<Tail> = Vec::new();
+    let preface_read: Result<Result<bool, ReadError>, Elapsed> = timeout(
+        server.config.idle_timeout,
+        read_preface(&mut stream, &mut buffer),
+    )
+    .await;
 
-    let adds: Vec<(String, String, Vec<Arc<Callback>>)> = server.adds;
-
-    for add in adds.iter() {
-        if !context.next {
-            break;
+    match preface_read {
+        Ok(Ok(true)) => {
+            http2(server, address, tls, shutdown, Rewind::new(buffer, stream)).await;
+            return;
         }
+        Ok(Ok(false)) => {}
+        _ => return,
+    }
 
-        let method_cp: String = if add.0 == "*" {
-            method.clone().to_lowercase()
-        } else {
-            add.0.to_lowercase()
-        };
+    let (mut reader, mut writer) = split(stream);
+    let mut served: usize = 0;
 
-        let path_cp: String = if add.1 == "*" {
-            path.clone().to_lowercase()
-        } else {
-            add.1.to_lowercase()
+    loop {
+        /*
+         * Wait for the next Request
+         */
+        let header_read: Result<Result<(String, Headers), ReadError>, Elapsed> =
+            timeout(server.config.idle_timeout, async {
+                /*
+                 * Idle Connection closes on Shutdown
+                 */
+                if buffer.is_empty()
+                    && !await_request(&mut reader, &mut buffer, &mut shutdown).await
+                {
+                    return Err(ReadError::Close);
+                }
+
+                get_header(&mut reader, &mut buffer, &server.config).await
+            })
+            .await;
+
+        let (header, headers) = match header_read {
+            Ok(Ok(x)) => x,
+            /*
+             * Timeout within a Request Header (not between Requests)
+             */
+            Err(_) if buffer.iter().any(|b: &u8| *b != b'\r' && *b != b'\n') => {
+                response_payload_error(&mut writer, 408).await;
+                return;
+            }
+            Ok(Err(ReadError::Close)) | Err(_) => {
+                response_payload_empty(&mut writer).await;
+                return;
+            }
+            Ok(Err(ReadError::Status(status))) => {
+                response_payload_error(&mut writer, status).await;
+                return;
+            }
         };
 
-        let callback_cp: Vec<Arc<Callback>> = add.2.to_owned();
+        served += 1;
+        let mut context: Context = new_context(address.clone(), header, headers, tls.clone()).await;
 
-        if method_cp != method.to_lowercase() {
-            continue;
+        let http_version: f64 = context.request.http_version;
+        /*
+         * Body
+         */
+        let body_read: Result<(), ReadError> = get_body(
+            &mut reader,
+            &mut writer,
+            &mut context.request,
+            &mut buffer,
+            &server.config,
+        )
+        .await;
+
+        match body_read {
+            Ok(_) => {}
+            Err(ReadError::Close) => return,
+            Err(ReadError::Status(status)) => {
+                response_payload_error(&mut writer, status).await;
+                return;
+            }
+        }
+        /*
+         * HTTP/2 Upgrade (h2c), only for Requests without Body
+         * With TLS, HTTP/2 is negotiated by ALPN instead
+         */
+        if tls.is_none() && is_http2_upgrade(&context.request) {
+            http2_upgrade(
+                server,
+                address,
+                shutdown,
+                reader,
+                writer,
+                buffer,
+                context.request,
+            )
+            .await;
+            return;
         }
         /*
-         * Static
+         * Persistent by default on HTTP/1.1, on request on HTTP/1.0
          */
-        if path_cp == path.to_lowercase() {
-            for callback in callback_cp.clone() {
-                context.next = false;
+        let mut keep_alive: bool = match http_version >= 1.1 {
+            true => !context.request.headers.has_token("connection", "close"),
+            false => context
+                .request
+                .headers
+                .has_token("connection", "keep-alive"),
+        };
 
-                context.request.param_store = Vec::new();
+        keep_alive = keep_alive && served < server.config.max_requests;
 
-                let callback_returns: Returns = (callback)(context).await;
+        context = dispatch(&server, context).await;
+        /*
+         * Last Response before Shutdown closes the Connection
+         */
+        keep_alive = keep_alive && !shutdown.is_set();
 
-                context = callback_returns.0;
+        let uploads: Vec<PathBuf> = context
+            .request
+            .parts
+            .iter()
+            .filter_map(|p: &Part| p.path.clone())
+            .collect();
+        /*
+         * WebSocket Upgrade (unless a Tail changed the Status)
+         */
+        let upgrade: Option<OnUpgrade> = match context.response.status {
+            101 => context.response.upgrade.take(),
+            _ => None,
+        };
 
-                if let Some(x) = callback_returns.1 {
-                    tails.push(x);
-                }
+        if let Some(x) = upgrade {
+            let max_message_size: usize = server.config.max_body_size;
+            response_upgrade(reader, writer, buffer, context, x, max_message_size).await;
 
-                if !context.next {
-                    break;
-                }
+            for path in uploads {
+                let _ = remove_file(path).await;
             }
 
-            continue;
+            return;
         }
         /*
-         * Dynamic
+         * Route may close the Connection
          */
-        let find_callback: IsFind = find_callback(path.to_owned(), path_cp).await;
+        if context.response.headers.has_token("connection", "close") {
+            keep_alive = false;
+        }
 
-        if find_callback.find {
-            for callback in callback_cp.clone() {
-                context.next = false;
+        context.response.headers.remove("connection");
 
-                context.request.param_store = find_callback.param.to_owned();
+        keep_alive = response_payload(&mut writer, context, http_version, keep_alive).await;
+        /*
+         * Remove Upload Files which were not persisted
+         */
+        for path in uploads {
+            let _ = remove_file(path).await;
+        }
 
-                let callback_returns: Returns = (callback)(context).await;
+        if !keep_alive {
+            return;
+        }
+    }
+}
 
-                context = callback_returns.0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::definition::{Callback, Returns};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
 
-                if let Some(x) = callback_returns.1 {
-                    tails.push(x);
-                }
+    async fn hello(mut c: Context) -> Returns {
+        c.response.body = "hello".into();
+        (c, None)
+    }
 
-                if !context.next {
-                    break;
-                }
-            }
-        }
+    async fn no_content(mut c: Context) -> Returns {
+        c.response.status = 204;
+        c.response.body = "ignored".into();
+        (c, None)
     }
+
     /*
-     * Route Not Found
+     * Everything written back for the given Requests
      */
-    if context.next {
+    async fn exchange(app: Server, requests: &[u8]) -> String {
+        let (mut client, server) = duplex(4096);
+        let (_sender, shutdown) = Shutdown::new();
+
+        let serve = tokio::spawn(handler(
+            app,
+            "test".to_owned(),
+            Box::new(server),
+            None,
+            shutdown,
+        ));
+
+        client.write_all(requests).await.unwrap();
+
+        let mut response: String = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        serve.await.unwrap();
+
+        response
+    }
+
+    #[tokio::test]
+    async fn pipelined_head_then_get() {
+        let mut app: Server = Server::new();
+        let route: Arc<Callback> = Arc::new(Box::new(|c: Context| Box::pin(hello(c))));
+        app.add(("head", "/", vec![route.clone()]));
+        app.add(("get", "/", vec![route]));
+
+        let response: String = exchange(
+            app,
+            b"HEAD / HTTP/1.1\r\nHost: a\r\n\r\n\
+              GET / HTTP/1.1\r\nHost: a\r\nConnection: close\r\n\r\n",
+        )
+        .await;
         /*
-         * Default Status & Body
+         * HEAD Response has the Framing of the Body, but no Body
          */
-        context.response.status = 404;
-        context.response.body = "Not Found".to_owned();
+        let (head, get) = response.split_at(response.rfind("HTTP/1.1").unwrap());
+
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.contains("Content-Length: 5\r\n"));
+        assert!(head.ends_with("\r\n\r\n"));
+
+        assert!(get.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(get.contains("Connection: close\r\n"));
+        assert!(get.ends_with("\r\n\r\nhello"));
     }
-    /*
-     * Tail
-     */
-    if !tails.is_empty() {
-        for i in tails.iter().rev() {
-            context = (i)(context).await;
-            if !context.next {
-                break;
-            }
-        }
+
+    #[tokio::test]
+    async fn no_content_has_no_body() {
+        let mut app: Server = Server::new();
+        let route: Arc<Callback> = Arc::new(Box::new(|c: Context| Box::pin(no_content(c))));
+        app.add(("get", "/", vec![route]));
+
+        let response: String = exchange(
+            app,
+            b"GET / HTTP/1.1\r\nHost: a\r\n\r\n\
+              GET / HTTP/1.1\r\nHost: a\r\nConnection: close\r\n\r\n",
+        )
+        .await;
+
+        assert_eq!(response.matches("HTTP/1.1 204 No Content\r\n").count(), 2);
+        assert!(!response.contains("Content-Length"));
+        assert!(!response.contains("ignored"));
     }
 
-    response_payload(writer, context, http_version).await;
+    #[tokio::test]
+    async fn header_timeout() {
+        let mut app: Server = Server::new();
+        app.set_idle_timeout(Duration::from_millis(50));
+        /*
+         * Idle Connection closes silently
+         */
+        assert_eq!(exchange(app.clone(), b"\r\n").await, "");
+        /*
+         * Partial Header is answered with 408
+         */
+        let response: String = exchange(app, b"GET / HTTP/1.1\r\nHo").await;
+
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+    }
 }
@@ -0,0 +1,553 @@
+use crate::server::Server;
+use crate::structs::body::ChunkStream;
+use crate::structs::context::Context;
+use crate::structs::definition::ReadError;
+use crate::structs::headers::Headers;
+use crate::structs::part::Part;
+use crate::structs::request::Request;
+use crate::structs::shutdown::Shutdown;
+use crate::structs::tls::TlsInfo;
+use crate::utils::body_sink::BodySink;
+use crate::utils::dispatch::dispatch;
+use crate::utils::new_context::new_context;
+use crate::utils::status_string::status_string;
+use bytes::Bytes;
+use futures::future::poll_fn;
+use futures::StreamExt;
+use h2::server::{Builder, Connection, SendResponse};
+use h2::{Reason, RecvStream, SendStream};
+use http::header::{HeaderName, HeaderValue};
+use http::request::Parts;
+use http::StatusCode;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::fs::remove_file;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::timeout;
+use tokio::{select, spawn};
+
+/*
+ * Connection-specific Fields are not allowed in HTTP/2
+ */
+const HOP_HEADERS: [&str; 6] = [
+    "connection",
+    "content-length",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/*
+ * Serve an HTTP/2 Connection, every Stream goes through the Router
+ */
+pub(crate) async fn http2<T>(
+    server: Server,
+    address: String,
+    tls: Option<TlsInfo>,
+    mut shutdown: Shutdown,
+    io: T,
+) where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let handshake = Builder::new()
+        .max_concurrent_streams(256)
+        .max_header_list_size(server.config.max_header_size as u32)
+        .handshake::<T, Bytes>(io);
+
+    let mut connection: Connection<T, Bytes> =
+        match timeout(server.config.idle_timeout, handshake).await {
+            Ok(Ok(x)) => x,
+            Ok(Err(e)) => {
+                println!("[Error] Fail to Handshake HTTP/2:\n{}", e);
+                return;
+            }
+            Err(_) => return,
+        };
+    /*
+     * Streams in flight, an idle Connection has none
+     */
+    let active: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    let mut served: usize = 0;
+    let mut closing: bool = false;
+
+    loop {
+        let accept = timeout(server.config.idle_timeout, connection.accept());
+        /*
+         * Shutdown: GOAWAY, running Streams finish
+         */
+        let accept_shutdown = select! {
+            x = accept => Some(x),
+            _ = shutdown.wait(), if !closing => None,
+        };
+
+        let accepted = match accept_shutdown {
+            None => {
+                connection.graceful_shutdown();
+                closing = true;
+                continue;
+            }
+            Some(Ok(x)) => x,
+            /*
+             * Idle Timeout: running Streams keep the Connection open, also
+             * while closing (Shutdown drains them up to its own timeout)
+             */
+            Some(Err(_)) => {
+                if active.load(Ordering::SeqCst) > 0 {
+                    continue;
+                }
+
+                if closing {
+                    break;
+                }
+
+                connection.graceful_shutdown();
+                closing = true;
+                continue;
+            }
+        };
+
+        let (request, respond) = match accepted {
+            Some(Ok(x)) => x,
+            Some(Err(e)) => {
+                if !e.is_go_away() && !e.is_io() {
+                    println!("[Error] Error in HTTP/2 Connection:\n{}", e);
+                }
+                break;
+            }
+            None => break,
+        };
+
+        served += 1;
+        active.fetch_add(1, Ordering::SeqCst);
+
+        let stream_active: Arc<AtomicUsize> = active.clone();
+        let stream_server: Server = server.clone();
+        let stream_address: String = address.clone();
+        let stream_tls: Option<TlsInfo> = tls.clone();
+
+        spawn(async move {
+            http2_stream(stream_server, stream_address, stream_tls, request, respond).await;
+            stream_active.fetch_sub(1, Ordering::SeqCst);
+        });
+        /*
+         * Maximum Requests: let running Streams finish, accept no new ones
+         */
+        if served >= server.config.max_requests && !closing {
+            connection.graceful_shutdown();
+            closing = true;
+        }
+    }
+}
+
+/*
+ * One Request / Response exchange
+ */
+async fn http2_stream(
+    server: Server,
+    address: String,
+    tls: Option<TlsInfo>,
+    request: http::Request<RecvStream>,
+    mut respond: SendResponse<Bytes>,
+) {
+    let (parts, mut body) = request.into_parts();
+
+    let (header, headers) = http2_header(&parts);
+
+    let mut context: Context = new_context(address, header, headers, tls).await;
+    /*
+     * Body
+     */
+    let body_read: Result<(), ReadError> =
+        read_body(&mut body, &mut respond, &mut context.request, &server).await;
+
+    match body_read {
+        Ok(_) => {}
+        Err(ReadError::Close) => return,
+        Err(ReadError::Status(status)) => {
+            send_error(respond, status).await;
+            /*
+             * Dropping the Body early would reset the Stream before the
+             * Response is out, discard it until the Client stops sending
+             */
+            let _ = timeout(server.config.idle_timeout, drain_body(&mut body)).await;
+            return;
+        }
+    }
+
+    context = dispatch(&server, context).await;
+
+    let uploads: Vec<PathBuf> = context
+        .request
+        .parts
+        .iter()
+        .filter_map(|p: &Part| p.path.clone())
+        .collect();
+
+    send_response(respond, context).await;
+    /*
+     * Remove Upload Files which were not persisted
+     */
+    for path in uploads {
+        let _ = remove_file(path).await;
+    }
+}
+
+/*
+ * Raw Header in HTTP/1 form, so the Request is parsed the same way
+ */
+fn http2_header(parts: &Parts) -> (String, Headers) {
+    let target: &str = parts
+        .uri
+        .path_and_query()
+        .map(|x| x.as_str())
+        .unwrap_or("/");
+
+    let mut headers: Headers = Headers::new();
+    /*
+     * :authority replaces Host
+     */
+    if let Some(x) = parts.uri.authority() {
+        if !parts.headers.contains_key("host") {
+            headers.append("host", x.as_str());
+        }
+    }
+
+    for (k, v) in parts.headers.iter() {
+        headers.append(k.as_str(), &String::from_utf8_lossy(v.as_bytes()));
+    }
+
+    let mut header: String = format!("{} {} HTTP/2.0\r\n", parts.method, target);
+
+    for (k, v) in &headers {
+        header.push_str(&format!("{}: {}\r\n", k, v));
+    }
+
+    (header, headers)
+}
+
+/*
+ * Read Request Body (DATA Frames) into the Sink
+ */
+async fn read_body(
+    body: &mut RecvStream,
+    respond: &mut SendResponse<Bytes>,
+    request: &mut Request,
+    server: &Server,
+) -> Result<(), ReadError> {
+    if body.is_end_stream() {
+        return Ok(());
+    }
+
+    let mut sink: BodySink = BodySink::new(request, &server.config).await?;
+
+    let length: Option<usize> = request
+        .headers
+        .get("content-length")
+        .and_then(|v: &str| v.trim().parse().ok());
+
+    if length.unwrap_or_default() > sink.limit(&server.config) {
+        return Err(ReadError::Status(413));
+    }
+    /*
+     * Client waits for approval before sending the Body
+     */
+    if request.headers.has_token("expect", "100-continue") {
+        let continue_100: http::Response<()> = http::Response::builder()
+            .status(StatusCode::CONTINUE)
+            .body(())
+            .unwrap_or_default();
+
+        let _ = respond.send_informational(continue_100);
+    }
+
+    let data_read: Result<(), ReadError> = timeout(
+        server.config.body_timeout,
+        read_data(body, &mut sink, request),
+    )
+    .await
+    .unwrap_or(Err(ReadError::Status(408)));
+
+    let body_read: Result<(), ReadError> = match data_read {
+        Ok(_) => sink.finish(request).await,
+        Err(e) => Err(e),
+    };
+    /*
+     * Remove partial Uploads
+     */
+    if body_read.is_err() {
+        sink.abort().await;
+    }
+
+    body_read
+}
+
+/*
+ * Discard remaining DATA Frames
+ */
+async fn drain_body(body: &mut RecvStream) {
+    while let Some(Ok(chunk)) = body.data().await {
+        let _ = body.flow_control().release_capacity(chunk.len());
+    }
+}
+
+/*
+ * DATA Frames & Trailers
+ */
+async fn read_data(
+    body: &mut RecvStream,
+    sink: &mut BodySink,
+    request: &mut Request,
+) -> Result<(), ReadError> {
+    while let Some(chunk) = body.data().await {
+        let chunk: Bytes = chunk.map_err(|_| ReadError::Close)?;
+        /*
+         * Let the Client send more
+         */
+        let _ = body.flow_control().release_capacity(chunk.len());
+
+        sink.write(&chunk).await?;
+    }
+
+    if let Ok(Some(trailers)) = body.trailers().await {
+        for (k, v) in trailers.iter() {
+            request
+                .trailers
+                .append(k.as_str(), &String::from_utf8_lossy(v.as_bytes()));
+        }
+    }
+
+    Ok(())
+}
+
+/*
+ * Write Response HEADERS & DATA Frames
+ */
+async fn send_response(mut respond: SendResponse<Bytes>, context: Context) {
+    let status: StatusCode = match StatusCode::from_u16(context.response.status as u16) {
+        Ok(x) if x.as_u16() >= 200 => x,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    let stream: Option<ChunkStream> = context.response.body.take_stream();
+
+    let mut response: http::Response<()> = http::Response::new(());
+    *response.status_mut() = status;
+
+    for (k, v) in &context.response.headers {
+        if HOP_HEADERS.iter().any(|h: &&str| k.eq_ignore_ascii_case(h)) {
+            continue;
+        }
+
+        match (
+            HeaderName::from_bytes(k.as_bytes()),
+            HeaderValue::from_str(v),
+        ) {
+            (Ok(k), Ok(v)) => {
+                response.headers_mut().append(k, v);
+            }
+            _ => println!("[Error] Skip invalid Response Header: {}", k),
+        }
+    }
+
+    if let Ok(x) = HeaderValue::from_str(&context.response.content_type) {
+        response.headers_mut().insert("content-type", x);
+    }
+
+    if stream.is_none() {
+        response
+            .headers_mut()
+            .insert("content-length", context.response.body.len().into());
+    }
+
+    let end: bool = stream.is_none() && context.response.body.is_empty();
+
+    let mut send: SendStream<Bytes> = match respond.send_response(response, end) {
+        Ok(x) => x,
+        Err(e) => {
+            println!("[Error] Fail to Write HTTP/2 Response:\n{}", e);
+            return;
+        }
+    };
+
+    if end {
+        return;
+    }
+
+    let body_write: Result<(), h2::Error> = match stream {
+        Some(x) => send_stream(&mut send, x).await,
+        None => {
+            let data: Bytes = Bytes::copy_from_slice(context.response.body.bytes());
+            send_data(&mut send, data, true).await
+        }
+    };
+
+    if let Err(e) = body_write {
+        println!("[Error] Fail to Write HTTP/2 Body:\n{}", e);
+    }
+}
+
+/*
+ * Reject Request with a plain Status Response
+ */
+async fn send_error(mut respond: SendResponse<Bytes>, status: usize) {
+    let status_str: String = status_string(status).await;
+
+    let mut response: http::Response<()> = http::Response::new(());
+    *response.status_mut() = StatusCode::from_u16(status as u16).unwrap_or(StatusCode::BAD_REQUEST);
+    response
+        .headers_mut()
+        .insert("content-type", HeaderValue::from_static("text/plain"));
+    response
+        .headers_mut()
+        .insert("content-length", status_str.len().into());
+
+    if let Ok(mut send) = respond.send_response(response, false) {
+        let _ = send_data(&mut send, Bytes::from(status_str), true).await;
+    }
+}
+
+/*
+ * Write Chunks as produced, an Err Chunk resets the Stream
+ */
+async fn send_stream(
+    send: &mut SendStream<Bytes>,
+    mut stream: ChunkStream,
+) -> Result<(), h2::Error> {
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            Ok(x) => send_data(send, Bytes::from(x), false).await?,
+            Err(_) => {
+                send.send_reset(Reason::INTERNAL_ERROR);
+                return Ok(());
+            }
+        }
+    }
+
+    send.send_data(Bytes::new(), true)
+}
+
+/*
+ * Write Data as the Flow Control Window allows (backpressure)
+ */
+async fn send_data(
+    send: &mut SendStream<Bytes>,
+    mut data: Bytes,
+    end: bool,
+) -> Result<(), h2::Error> {
+    while !data.is_empty() {
+        send.reserve_capacity(data.len());
+
+        let capacity: usize = match poll_fn(|cx| send.poll_capacity(cx)).await {
+            Some(x) => x?,
+            None => return Err(Reason::CANCEL.into()),
+        };
+
+        if capacity == 0 {
+            continue;
+        }
+
+        let chunk: Bytes = data.split_to(capacity.min(data.len()));
+        send.send_data(chunk, false)?;
+    }
+
+    if end {
+        send.send_data(Bytes::new(), true)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::Server;
+    use crate::structs::context::Context;
+    use crate::structs::definition::{Callback, Returns};
+    use crate::structs::shutdown::Shutdown;
+    use crate::structs::websocket::WebSocket;
+    use crate::utils::handler::handler;
+    use bytes::Bytes;
+    use h2::client::{handshake, SendRequest};
+    use http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tokio::io::duplex;
+    use tokio::spawn;
+
+    async fn hello(mut c: Context) -> Returns {
+        c.response.body = format!("hello {}", c.request.method).into();
+        (c, None)
+    }
+
+    async fn echo(_c: Context, _ws: WebSocket) {}
+
+    /*
+     * HTTP/2 Client (Prior Knowledge) of a served Connection
+     */
+    async fn client(app: Server) -> SendRequest<Bytes> {
+        let (client, server) = duplex(64 * 1024);
+        let (sender, shutdown) = Shutdown::new();
+
+        spawn(async move {
+            handler(app, "test".to_owned(), Box::new(server), None, shutdown).await;
+            drop(sender);
+        });
+
+        let (send, connection) = handshake(client).await.unwrap();
+        spawn(connection);
+
+        send
+    }
+
+    #[tokio::test]
+    async fn websocket_is_not_implemented() {
+        let mut app: Server = Server::new();
+        app.add(crate::websocket!("/chat", echo));
+
+        let mut send: SendRequest<Bytes> = client(app).await;
+
+        let request: Request<()> = Request::get("http://a/chat").body(()).unwrap();
+        let (response, _) = send.send_request(request, true).unwrap();
+
+        assert_eq!(
+            response.await.unwrap().status(),
+            StatusCode::NOT_IMPLEMENTED
+        );
+    }
+
+    #[tokio::test]
+    async fn prior_knowledge() {
+        let mut app: Server = Server::new();
+        let route: Arc<Callback> = Arc::new(Box::new(|c: Context| Box::pin(hello(c))));
+        app.add(("*", "/", vec![route]));
+
+        let mut send: SendRequest<Bytes> = client(app).await;
+        /*
+         * Two Streams on one Connection
+         */
+        for method in ["GET", "POST"] {
+            let request: Request<()> = Request::builder()
+                .method(method)
+                .uri("http://a/")
+                .body(())
+                .unwrap();
+            let (response, _) = send.send_request(request, true).unwrap();
+            let response = response.await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()["content-length"],
+                (method.len() + 6).to_string().as_str()
+            );
+
+            let mut body = response.into_body();
+            let mut data: Vec<u8> = Vec::new();
+
+            while let Some(chunk) = body.data().await {
+                data.extend_from_slice(&chunk.unwrap());
+            }
+
+            assert_eq!(data, format!("hello {}", method).as_bytes());
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use crate::server::Server;
+use crate::structs::definition::{IoReader, IoStream, IoWriter, ReadError};
+use crate::structs::headers::Headers;
+use crate::structs::request::Request;
+use crate::structs::shutdown::Shutdown;
+use crate::utils::http2::http2;
+use crate::utils::read_preface::{read_preface, PREFACE};
+use crate::utils::rewind::Rewind;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use http::header::{HeaderName, HeaderValue};
+use std::slice::ChunksExact;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, Error};
+use tokio::time::timeout;
+
+/*
+ * Fields of the upgraded Request not carried over to HTTP/2
+ */
+const SKIP_HEADERS: [&str; 8] = [
+    "connection",
+    "host",
+    "http2-settings",
+    "keep-alive",
+    "proxy-connection",
+    "te",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/*
+ * Upgrade only HTTP/1.1 Requests without Body, with an origin-form Target
+ * and exactly one valid HTTP2-Settings Field (RFC 7540 3.2.1)
+ * Other Requests are answered over HTTP/1.1
+ */
+pub(crate) fn is_http2_upgrade(request: &Request) -> bool {
+    let headers: &Headers = &request.headers;
+    let settings: Vec<&str> = headers.get_all("http2-settings");
+
+    request.http_version == 1.1
+        && request.url.starts_with('/')
+        && headers.has_token("upgrade", "h2c")
+        && headers.has_token("connection", "http2-settings")
+        && settings.len() == 1
+        && valid_settings(settings[0])
+        && !headers.contains("transfer-encoding")
+        && headers
+            .get("content-length")
+            .map(|v: &str| v.trim())
+            .unwrap_or("0")
+            == "0"
+}
+
+/*
+ * HTTP2-Settings: SETTINGS Frame Payload in base64url without Padding
+ * Only validated, the Client sends its Settings again in the SETTINGS
+ * Frame after the Preface, which h2 applies
+ */
+fn valid_settings(value: &str) -> bool {
+    let payload: Vec<u8> = match URL_SAFE_NO_PAD.decode(value.trim().trim_end_matches('=')) {
+        Ok(x) => x,
+        Err(_) => return false,
+    };
+
+    let mut settings: ChunksExact<u8> = payload.chunks_exact(6);
+
+    settings.remainder().is_empty()
+        && settings.all(|x: &[u8]| {
+            let value: u32 = u32::from_be_bytes([x[2], x[3], x[4], x[5]]);
+
+            match u16::from_be_bytes([x[0], x[1]]) {
+                /* ENABLE_PUSH */
+                0x2 => value <= 1,
+                /* INITIAL_WINDOW_SIZE */
+                0x4 => value <= 0x7FFF_FFFF,
+                /* MAX_FRAME_SIZE */
+                0x5 => (16_384..=16_777_215).contains(&value),
+                _ => true,
+            }
+        })
+}
+
+/*
+ * Switch to HTTP/2 (Upgrade: h2c)
+ * The upgraded Request becomes Stream 1, answered over HTTP/2
+ */
+pub(crate) async fn http2_upgrade(
+    server: Server,
+    address: String,
+    shutdown: Shutdown,
+    reader: IoReader,
+    mut writer: IoWriter,
+    mut buffer: Vec<u8>,
+    request: Request,
+) {
+    let stream_write: Result<(), Error> = writer
+        .write_all(
+            b"HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\nUpgrade: h2c\r\n\r\n",
+        )
+        .await;
+
+    if stream_write.is_err() {
+        println!(
+            "[Error] Fail to Write Upgrade Stream:\n{}",
+            stream_write.err().unwrap()
+        );
+
+        return;
+    }
+
+    let mut stream: IoStream = reader.unsplit(writer);
+    /*
+     * Client Preface & its first SETTINGS Frame
+     */
+    let preface_read = timeout(
+        server.config.idle_timeout,
+        read_settings(&mut stream, &mut buffer),
+    )
+    .await;
+
+    let end: usize = match preface_read {
+        Ok(Ok(x)) => x,
+        _ => return,
+    };
+    /*
+     * Stream 1 HEADERS go right after the SETTINGS Frame
+     */
+    let mut prefix: Vec<u8> = buffer[..end].to_vec();
+    prefix.extend_from_slice(&header_frames(&request));
+    prefix.extend_from_slice(&buffer[end..]);
+
+    http2(
+        server,
+        address,
+        request.tls,
+        shutdown,
+        Rewind::new(prefix, stream),
+    )
+    .await;
+}
+
+/*
+ * Read Preface & first Frame (must be SETTINGS), returns where it ends
+ */
+async fn read_settings(stream: &mut IoStream, buffer: &mut Vec<u8>) -> Result<usize, ReadError> {
+    if !read_preface(stream, buffer).await? {
+        return Err(ReadError::Close);
+    }
+
+    let mut chunk: [u8; 4096] = [0; 4096];
+
+    loop {
+        let start: usize = PREFACE.len();
+
+        if buffer.len() >= start + 9 {
+            if buffer[start + 3] != 0x4 {
+                return Err(ReadError::Close);
+            }
+
+            let length: usize = (buffer[start] as usize) << 16
+                | (buffer[start + 1] as usize) << 8
+                | buffer[start + 2] as usize;
+
+            if buffer.len() >= start + 9 + length {
+                return Ok(start + 9 + length);
+            }
+        }
+
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err(ReadError::Close),
+            Ok(x) => buffer.extend_from_slice(&chunk[..x]),
+        }
+    }
+}
+
+/*
+ * HEADERS (+ CONTINUATION) Frames of Stream 1, no Body follows
+ * h2 has no API to inject a Request, so the Frames are placed in its
+ * input: Literals without Indexing leave the HPACK Table untouched, and
+ * only Fields valid in HTTP/2 are sent, so h2 decodes what a Client sends
+ */
+fn header_frames(request: &Request) -> Vec<u8> {
+    let mut block: Vec<u8> = Vec::new();
+
+    hpack_literal(&mut block, ":method", &request.method);
+    hpack_literal(&mut block, ":scheme", "http");
+
+    if let Some(x) = request.headers.get("host") {
+        hpack_literal(&mut block, ":authority", x.trim());
+    }
+
+    hpack_literal(&mut block, ":path", &request.url);
+
+    for (k, v) in &request.headers {
+        let k: String = k.to_lowercase();
+
+        if SKIP_HEADERS.contains(&k.as_str()) {
+            continue;
+        }
+
+        if HeaderName::from_bytes(k.as_bytes()).is_err() || HeaderValue::from_str(v).is_err() {
+            println!("[Error] Skip invalid HTTP/2 Upgrade Header: {}", k);
+            continue;
+        }
+
+        hpack_literal(&mut block, &k, v);
+    }
+    /*
+     * Default Maximum Frame Size
+     */
+    let chunks: Vec<&[u8]> = block.chunks(16384).collect();
+    let mut frames: Vec<u8> = Vec::new();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let last: bool = i == chunks.len() - 1;
+        /*
+         * HEADERS (END_STREAM) then CONTINUATION, END_HEADERS on the last
+         */
+        let (kind, mut flags): (u8, u8) = match i {
+            0 => (0x1, 0x1),
+            _ => (0x9, 0x0),
+        };
+
+        if last {
+            flags |= 0x4;
+        }
+
+        frames.extend_from_slice(&(chunk.len() as u32).to_be_bytes()[1..]);
+        frames.push(kind);
+        frames.push(flags);
+        frames.extend_from_slice(&1u32.to_be_bytes());
+        frames.extend_from_slice(chunk);
+    }
+
+    frames
+}
+
+/*
+ * HPACK Literal Header Field without Indexing, new Name, no Huffman
+ */
+fn hpack_literal(block: &mut Vec<u8>, name: &str, value: &str) {
+    block.push(0x00);
+
+    for x in [name, value] {
+        hpack_integer(block, x.len());
+        block.extend_from_slice(x.as_bytes());
+    }
+}
+
+/*
+ * HPACK Integer with 7-bit Prefix (String Length)
+ */
+fn hpack_integer(block: &mut Vec<u8>, mut value: usize) {
+    if value < 127 {
+        block.push(value as u8);
+        return;
+    }
+
+    block.push(127);
+    value -= 127;
+
+    while value >= 128 {
+        block.push((value % 128 + 128) as u8);
+        value /= 128;
+    }
+
+    block.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::context::Context;
+    use crate::structs::definition::{Callback, Returns};
+    use crate::utils::handler::handler;
+    use std::sync::Arc;
+    use tokio::io::{duplex, DuplexStream};
+    use tokio::spawn;
+
+    async fn hello(mut c: Context) -> Returns {
+        c.response.body = "hello".into();
+        (c, None)
+    }
+
+    /*
+     * Client end of a served Connection
+     */
+    fn connect() -> DuplexStream {
+        let mut app: Server = Server::new();
+        let route: Arc<Callback> = Arc::new(Box::new(|c: Context| Box::pin(hello(c))));
+        app.add(("get", "/", vec![route]));
+
+        let (client, server) = duplex(64 * 1024);
+        let (sender, shutdown) = Shutdown::new();
+
+        spawn(async move {
+            handler(app, "test".to_owned(), Box::new(server), None, shutdown).await;
+            drop(sender);
+        });
+
+        client
+    }
+
+    /*
+     * Next Frame: (Type, Flags, Stream, Payload)
+     */
+    async fn frame(client: &mut DuplexStream) -> (u8, u8, u32, Vec<u8>) {
+        let mut head: [u8; 9] = [0; 9];
+        client.read_exact(&mut head).await.unwrap();
+
+        let length: usize = u32::from_be_bytes([0, head[0], head[1], head[2]]) as usize;
+        let stream: u32 = u32::from_be_bytes([head[5], head[6], head[7], head[8]]) & 0x7FFF_FFFF;
+
+        let mut payload: Vec<u8> = vec![0; length];
+        client.read_exact(&mut payload).await.unwrap();
+
+        (head[3], head[4], stream, payload)
+    }
+
+    #[test]
+    fn settings() {
+        assert!(valid_settings(""));
+        assert!(valid_settings("AAMAAABkAAQAAP__"));
+        assert!(valid_settings("AAMAAABkAAQAAP__=="));
+        /* ENABLE_PUSH 2 */
+        assert!(!valid_settings("AAIAAAAC"));
+        /* MAX_FRAME_SIZE 1 */
+        assert!(!valid_settings("AAUAAAAB"));
+        /* Not a multiple of 6 Bytes */
+        assert!(!valid_settings("AAMAAA"));
+        assert!(!valid_settings("AA*AAABk"));
+    }
+
+    #[tokio::test]
+    async fn upgrade() {
+        let mut client: DuplexStream = connect();
+
+        client
+            .write_all(
+                b"GET / HTTP/1.1\r\nHost: a\r\nConnection: Upgrade, HTTP2-Settings\r\n\
+                  Upgrade: h2c\r\nHTTP2-Settings: AAMAAABkAAQAAP__\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        let switching: &[u8] =
+            b"HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\nUpgrade: h2c\r\n\r\n";
+        let mut response: Vec<u8> = vec![0; switching.len()];
+        client.read_exact(&mut response).await.unwrap();
+
+        assert_eq!(response, switching);
+        /*
+         * Client Preface & empty SETTINGS, the Response comes on Stream 1
+         */
+        client.write_all(PREFACE).await.unwrap();
+        client
+            .write_all(&[0, 0, 0, 0x4, 0, 0, 0, 0, 0])
+            .await
+            .unwrap();
+
+        let mut status: Option<u8> = None;
+        let mut body: Vec<u8> = Vec::new();
+
+        loop {
+            let (kind, flags, stream, payload) = frame(&mut client).await;
+
+            match (kind, stream) {
+                (0x1, 1) => status = payload.first().copied(),
+                (0x0, 1) => body.extend_from_slice(&payload),
+                _ => {}
+            }
+
+            if stream == 1 && flags & 0x1 != 0 {
+                break;
+            }
+        }
+        /*
+         * Indexed Field 8 of the HPACK Static Table is `:status: 200`
+         */
+        assert_eq!(status, Some(0x88));
+        assert_eq!(body, b"hello");
+    }
+
+    #[tokio::test]
+    async fn upgrade_needs_settings() {
+        for settings in ["", "HTTP2-Settings: AAIAAAAC\r\n"] {
+            let mut client: DuplexStream = connect();
+
+            client
+                .write_all(
+                    format!(
+                        "GET / HTTP/1.1\r\nHost: a\r\nConnection: Upgrade, HTTP2-Settings, close\r\n\
+                         Upgrade: h2c\r\n{}\r\n",
+                        settings
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+
+            let mut response: String = String::new();
+            client.read_to_string(&mut response).await.unwrap();
+
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+            assert!(response.ends_with("\r\n\r\nhello"));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/*
+ * Format Time as HTTP Date (IMF-fixdate)
+ * Sun, 06 Nov 1994 08:49:37 GMT
+ */
+pub(crate) fn http_date(time: SystemTime) -> String {
+    let secs: u64 = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let days: u64 = secs / 86400;
+    let rem: u64 = secs % 86400;
+    /*
+     * Civil Date from Days since 1970-01-01 (Thursday)
+     */
+    let z: u64 = days + 719468;
+    let era: u64 = z / 146097;
+    let doe: u64 = z - era * 146097;
+    let yoe: u64 = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy: u64 = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp: u64 = (5 * doy + 2) / 153;
+    let day: u64 = doy - (153 * mp + 2) / 5 + 1;
+    let month: u64 = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year: u64 = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        DAYS[((days + 4) % 7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,243 @@
+use crate::utils::socket_id::socket_id;
+use socket2::SockRef;
+use std::env::var;
+use std::os::fd::{BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/*
+ * First passed Descriptor (after stdin, stdout & stderr)
+ */
+pub(crate) const LISTEN_FDS_START: RawFd = 3;
+
+/*
+ * Sockets handed over by `ServerHandle::upgrade`, by Identity (see socket_id)
+ * The new Process' pid is not known before it starts, so there is no LISTEN_PID
+ */
+pub(crate) const LISTEN_SOCKETS: &str = "OXIDY_LISTEN_SOCKETS";
+
+/*
+ * Descriptors are handed out only once per Process
+ */
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Take Sockets passed by systemd Socket Activation
+///
+/// Reads `LISTEN_PID` / `LISTEN_FDS` (the sockets start at descriptor 3).
+/// The variables are left as they are (changing the environment while other
+/// threads run is not safe), child processes ignore them since `LISTEN_PID`
+/// names this process. Sockets handed over by `ServerHandle::upgrade` come
+/// the same way, named by `OXIDY_LISTEN_SOCKETS` instead of `LISTEN_PID`.
+/// Empty without socket activation or when called a second time.
+///
+/// `Server::bind_systemd` adopts all of them, use this to give each socket
+/// its own setup (e.g. TLS on the second one).
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{listen_fds, Server, TlsConfig};
+///
+/// async fn start() -> std::io::Result<()> {
+///     let app = Server::new();
+///
+///     let mut fds = listen_fds().into_iter();
+///
+///     if let (Some(http), Some(https)) = (fds.next(), fds.next()) {
+///         let mut bound = app.bind_fd(http)?;
+///         bound.listen_fd_tls(https, TlsConfig::new("cert.pem", "key.pem")).await?;
+///         /* bound.run().await; */
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn listen_fds() -> Vec<OwnedFd> {
+    let (count, sockets) = match listen_env(
+        var("LISTEN_PID").ok(),
+        var("LISTEN_FDS").ok(),
+        var(LISTEN_SOCKETS).ok(),
+    ) {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+
+    if TAKEN.swap(true, Ordering::SeqCst) {
+        return Vec::new();
+    }
+
+    adopt_fds(LISTEN_FDS_START, count, sockets.as_deref())
+}
+
+/*
+ * Number of passed Descriptors, with the Identities they must have when
+ * handed over (None when activated by systemd)
+ * None when the Variables are not meant for this Process
+ */
+fn listen_env(
+    pid: Option<String>,
+    count: Option<String>,
+    sockets: Option<String>,
+) -> Option<(RawFd, Option<Vec<String>>)> {
+    let count: RawFd = count
+        .and_then(|x: String| x.trim().parse().ok())
+        .filter(|x: &RawFd| *x > 0)?;
+
+    match (pid, sockets) {
+        /*
+         * Meant for this Process only, not for one it was inherited from
+         */
+        (Some(pid), _) => match pid.trim().parse::<u32>() {
+            Ok(x) if x == process::id() => Some((count, None)),
+            _ => None,
+        },
+        /*
+         * Handed over: every Descriptor must be the named Socket, which a
+         * Process inheriting the Variables does not have (the Sockets are
+         * close-on-exec)
+         */
+        (None, Some(sockets)) => Some((
+            count,
+            Some(sockets.split(',').map(|x: &str| x.to_owned()).collect()),
+        )),
+        (None, None) => None,
+    }
+}
+
+/*
+ * Own the listening Sockets among `count` Descriptors from `start` on
+ */
+fn adopt_fds(start: RawFd, count: RawFd, sockets: Option<&[String]>) -> Vec<OwnedFd> {
+    (start..start.saturating_add(count))
+        .filter_map(|fd: RawFd| {
+            /*
+             * A too large LISTEN_FDS names Descriptors which are not open
+             */
+            // SAFETY: F_GETFD only reads the Descriptor Flags, no Memory involved
+            if unsafe { libc::fcntl(fd, libc::F_GETFD) } < 0 {
+                println!("[Error] Descriptor {} is not open", fd);
+                return None;
+            }
+            // SAFETY: open (checked above) & only borrowed for the checks below,
+            // systemd (or the upgraded parent) passes it to this process
+            let borrowed: BorrowedFd = unsafe { BorrowedFd::borrow_raw(fd) };
+            /*
+             * A wrong LISTEN_FDS must not take over unrelated Descriptors
+             */
+            let socket: SockRef = SockRef::from(&borrowed);
+
+            if let Err(e) = socket.r#type() {
+                println!("[Error] Descriptor {} is not a Socket:\n{}", fd, e);
+                return None;
+            }
+            /*
+             * Nor Sockets which are not listening (e.g. the Runtime's own)
+             */
+            #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+            if !socket.is_listener().unwrap_or(false) {
+                println!("[Error] Socket {} is not listening", fd);
+                return None;
+            }
+            if let Some(sockets) = sockets {
+                let expected: Option<&String> = sockets.get((fd - start) as usize);
+
+                if socket_id(borrowed).ok().as_ref() != expected {
+                    println!("[Error] Descriptor {} is not a handed over Socket", fd);
+                    return None;
+                }
+            }
+            /*
+             * Not passed on to child Processes
+             */
+            if let Err(e) = socket.set_cloexec(true) {
+                println!("[Error] Fail to set close-on-exec on Socket {}:\n{}", fd, e);
+            }
+            // SAFETY: an open Socket passed to this process, the caller (TAKEN)
+            // makes sure nothing else in this process claims it
+            Some(unsafe { OwnedFd::from_raw_fd(fd) })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{TcpListener, UdpSocket};
+    use std::os::fd::{AsRawFd, IntoRawFd};
+
+    fn env(pid: Option<&str>, count: Option<&str>, sockets: Option<&str>) -> Option<RawFd> {
+        listen_env(
+            pid.map(|x: &str| x.to_owned()),
+            count.map(|x: &str| x.to_owned()),
+            sockets.map(|x: &str| x.to_owned()),
+        )
+        .map(|(count, _)| count)
+    }
+
+    #[test]
+    fn activated() {
+        let pid: String = process::id().to_string();
+
+        assert_eq!(
+            listen_env(Some(pid), Some("2".into()), None),
+            Some((2, None))
+        );
+    }
+
+    #[test]
+    fn pid_mismatch() {
+        let other: String = (process::id() + 1).to_string();
+        /*
+         * Inherited Variables, also with Identities present
+         */
+        assert_eq!(env(Some(&other), Some("1"), None), None);
+        assert_eq!(env(Some(&other), Some("1"), Some("1:2")), None);
+        assert_eq!(env(Some("abc"), Some("1"), Some("1:2")), None);
+        assert_eq!(env(None, Some("1"), None), None);
+    }
+
+    #[test]
+    fn bad_count() {
+        let pid: String = process::id().to_string();
+
+        for count in [None, Some(""), Some("abc"), Some("0"), Some("-2")] {
+            assert_eq!(env(Some(&pid), count, None), None);
+        }
+    }
+
+    #[test]
+    fn handed_over() {
+        assert_eq!(
+            listen_env(None, Some("2".into()), Some("1:2,3:4".into())),
+            Some((2, Some(vec!["1:2".to_owned(), "3:4".to_owned()])))
+        );
+    }
+
+    #[test]
+    fn adopt_socket_id() {
+        let fd: RawFd = TcpListener::bind("127.0.0.1:0").unwrap().into_raw_fd();
+        // SAFETY: fd was just released by the Listener above
+        let id: String = socket_id(unsafe { BorrowedFd::borrow_raw(fd) }).unwrap();
+        /*
+         * Another Socket's Identity: left alone
+         */
+        assert!(adopt_fds(fd, 1, Some(&["0:0".to_owned()])).is_empty());
+        assert!(adopt_fds(fd, 1, Some(&[])).is_empty());
+
+        let fds: Vec<OwnedFd> = adopt_fds(fd, 1, Some(&[id]));
+
+        assert_eq!(fds.len(), 1);
+        assert_eq!(fds[0].as_raw_fd(), fd);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "android", target_os = "freebsd", target_os = "linux"))]
+    fn adopt_listeners_only() {
+        let socket: UdpSocket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        assert!(adopt_fds(socket.as_raw_fd(), 1, None).is_empty());
+        /*
+         * Not open
+         */
+        assert!(adopt_fds(RawFd::MAX - 1, 1, None).is_empty());
+    }
+}
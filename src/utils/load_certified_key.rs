@@ -0,0 +1,42 @@
+use crate::structs::tls::TlsConfig;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::sign::CertifiedKey;
+use tokio::fs::read;
+use tokio::io::{Error, ErrorKind};
+
+/*
+ * Certificate Chain & matching Private Key from PEM Files
+ */
+pub(crate) async fn load_certified_key(
+    tls: &TlsConfig,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, Error> {
+    let cert_pem: Vec<u8> = read(&tls.cert_path).await?;
+    let key_pem: Vec<u8> = read(&tls.key_path).await?;
+
+    let certs: Vec<CertificateDer<'static>> =
+        rustls_pemfile::certs(&mut cert_pem.as_slice()).collect::<Result<_, Error>>()?;
+
+    if certs.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("No Certificate in {}", tls.cert_path.display()),
+        ));
+    }
+
+    let key: PrivateKeyDer<'static> = match rustls_pemfile::private_key(&mut key_pem.as_slice())? {
+        Some(x) => x,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("No Private Key in {}", tls.key_path.display()),
+            ))
+        }
+    };
+    /*
+     * Rejects a Key not belonging to the Certificate (e.g. half rotated Files)
+     */
+    CertifiedKey::from_der(certs, key, provider)
+        .map_err(|e: rustls::Error| Error::new(ErrorKind::InvalidData, e))
+}
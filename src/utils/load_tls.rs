@@ -0,0 +1,76 @@
+use crate::structs::tls::{ClientAuth, TlsConfig};
+use crate::utils::load_certified_key::load_certified_key;
+use rustls::crypto::ring::default_provider;
+use rustls::crypto::CryptoProvider;
+use rustls::server::danger::ClientCertVerifier;
+use rustls::server::{NoClientAuth, VerifierBuilderError, WebPkiClientVerifier};
+use rustls::{RootCertStore, ServerConfig};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs::read;
+use tokio::io::{Error, ErrorKind};
+
+/*
+ * Build rustls Server Config from PEM Files
+ * ALPN prefers HTTP/2, HTTP/1.1 otherwise
+ */
+pub(crate) async fn load_tls(tls: &TlsConfig) -> Result<ServerConfig, Error> {
+    let provider: Arc<CryptoProvider> = Arc::new(default_provider());
+    /*
+     * Certificate is served through the (reloadable) Resolver
+     */
+    tls.resolver.set(load_certified_key(tls, &provider).await?);
+    /*
+     * Mutual TLS
+     */
+    let verifier: Arc<dyn ClientCertVerifier> = match &tls.client_auth {
+        ClientAuth::None => Arc::new(NoClientAuth),
+        ClientAuth::Optional(path) => WebPkiClientVerifier::builder_with_provider(
+            Arc::new(load_roots(path).await?),
+            provider.clone(),
+        )
+        .allow_unauthenticated()
+        .build()
+        .map_err(|e: VerifierBuilderError| Error::new(ErrorKind::InvalidData, e))?,
+        ClientAuth::Required(path) => WebPkiClientVerifier::builder_with_provider(
+            Arc::new(load_roots(path).await?),
+            provider.clone(),
+        )
+        .build()
+        .map_err(|e: VerifierBuilderError| Error::new(ErrorKind::InvalidData, e))?,
+    };
+
+    let mut config: ServerConfig = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e: rustls::Error| Error::new(ErrorKind::InvalidInput, e))?
+        .with_client_cert_verifier(verifier)
+        .with_cert_resolver(tls.resolver.clone());
+
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+/*
+ * Client CA Bundle (PEM)
+ */
+async fn load_roots(path: &Path) -> Result<RootCertStore, Error> {
+    let pem: Vec<u8> = read(path).await?;
+
+    let mut roots: RootCertStore = RootCertStore::empty();
+
+    for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+        roots
+            .add(cert?)
+            .map_err(|e: rustls::Error| Error::new(ErrorKind::InvalidData, e))?;
+    }
+
+    if roots.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("No CA Certificate in {}", path.display()),
+        ));
+    }
+
+    Ok(roots)
+}
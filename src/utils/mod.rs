@@ -1,12 +1,48 @@
+#[cfg(unix)]
+pub(crate) mod adopt_listener;
+pub(crate) mod await_request;
+#[cfg(unix)]
+pub(crate) mod bind_unix;
+pub(crate) mod body_sink;
 pub(crate) mod del_vec;
+pub(crate) mod deserialize_pairs;
+pub(crate) mod dispatch;
 pub(crate) mod find_callback;
+pub(crate) mod get_body;
+pub(crate) mod get_body_chunked;
 pub(crate) mod get_header;
 pub(crate) mod get_vec;
+pub(crate) mod hand_over;
 pub(crate) mod handler;
+pub(crate) mod http2;
+pub(crate) mod http2_upgrade;
+pub(crate) mod http_date;
+#[cfg(unix)]
+pub(crate) mod listen_fds;
+pub(crate) mod load_certified_key;
+pub(crate) mod load_tls;
+pub(crate) mod multipart_parser;
+pub(crate) mod new_context;
+pub(crate) mod parse_headers;
 pub(crate) mod parse_http_version;
 pub(crate) mod parse_method;
+pub(crate) mod parse_mime;
 pub(crate) mod parse_path;
+pub(crate) mod parse_segments;
+pub(crate) mod parse_urlencoded;
+pub(crate) mod percent_decode;
+pub(crate) mod read_preface;
+pub(crate) mod response_headers;
 pub(crate) mod response_payload;
 pub(crate) mod response_payload_empty;
+pub(crate) mod response_payload_error;
+pub(crate) mod response_upgrade;
+pub(crate) mod rewind;
+pub(crate) mod serve;
 pub(crate) mod set_vec;
+pub(crate) mod shutdown_signal;
+#[cfg(unix)]
+pub(crate) mod socket_id;
 pub(crate) mod status_string;
+pub(crate) mod tls_info;
+pub(crate) mod watch_tls;
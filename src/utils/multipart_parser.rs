@@ -0,0 +1,449 @@
+use crate::structs::config::Config;
+use crate::structs::definition::ReadError;
+use crate::structs::headers::Headers;
+use crate::structs::part::Part;
+use crate::utils::parse_headers::parse_headers;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs::{remove_file, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+/*
+ * Longest accepted Part Header block
+ */
+const MAX_PART_HEADER: usize = 8 * 1024;
+
+static UPLOAD_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+enum State {
+    /* Before the first Boundary */
+    Preamble,
+    /* Right after a Boundary: "--" ends, CRLF starts a Part */
+    Delimiter,
+    Headers,
+    Data,
+    End,
+}
+
+/*
+ * Incremental multipart/form-data Parser
+ * File Parts are written to the Upload Directory as they arrive
+ */
+pub(crate) struct MultipartParser {
+    delimiter: Vec<u8>,
+    buffer: Vec<u8>,
+    state: State,
+    part: Option<Part>,
+    file: Option<File>,
+    pub(crate) parts: Vec<Part>,
+    total: usize,
+    fields: usize,
+    dir: PathBuf,
+    max_file_size: usize,
+    max_upload_size: usize,
+    max_field_size: usize,
+    max_parts: usize,
+    max_header_count: usize,
+}
+
+impl MultipartParser {
+    pub(crate) fn new(boundary: &str, config: &Config) -> MultipartParser {
+        MultipartParser {
+            delimiter: format!("\r\n--{}", boundary).into_bytes(),
+            /*
+             * First Boundary has no leading CRLF
+             */
+            buffer: b"\r\n".to_vec(),
+            state: State::Preamble,
+            part: None,
+            file: None,
+            parts: Vec::new(),
+            total: 0,
+            fields: 0,
+            dir: config.upload_dir.clone(),
+            max_file_size: config.max_file_size,
+            max_upload_size: config.max_upload_size,
+            max_field_size: config.max_body_size,
+            max_parts: config.max_parts,
+            max_header_count: config.max_header_count,
+        }
+    }
+
+    pub(crate) async fn write(&mut self, data: &[u8]) -> Result<(), ReadError> {
+        self.total += data.len();
+
+        if self.total > self.max_upload_size {
+            return Err(ReadError::Status(413));
+        }
+
+        self.buffer.extend_from_slice(data);
+
+        loop {
+            let progressed: bool = match self.state {
+                State::Preamble => self.preamble(),
+                State::Delimiter => self.after_delimiter()?,
+                State::Headers => self.headers().await?,
+                State::Data => self.data().await?,
+                State::End => {
+                    self.buffer.clear();
+                    false
+                }
+            };
+
+            if !progressed {
+                return Ok(());
+            }
+        }
+    }
+
+    pub(crate) async fn finish(&mut self) -> Result<Vec<Part>, ReadError> {
+        match self.state {
+            State::End => Ok(std::mem::take(&mut self.parts)),
+            _ => Err(ReadError::Status(400)),
+        }
+    }
+    /*
+     * Remove every written File after a failure
+     */
+    pub(crate) async fn abort(&mut self) {
+        self.file = None;
+
+        let paths = self
+            .parts
+            .iter()
+            .chain(self.part.iter())
+            .filter_map(|p: &Part| p.path.clone());
+
+        for path in paths {
+            let _ = remove_file(path).await;
+        }
+    }
+
+    fn find(&self, needle: &[u8]) -> Option<usize> {
+        self.buffer
+            .windows(needle.len())
+            .position(|w: &[u8]| w == needle)
+    }
+
+    fn preamble(&mut self) -> bool {
+        match self.find(&self.delimiter.clone()) {
+            Some(i) => {
+                self.buffer.drain(..i + self.delimiter.len());
+                self.state = State::Delimiter;
+                true
+            }
+            None => {
+                let keep: usize = self.buffer.len().min(self.delimiter.len());
+                self.buffer.drain(..self.buffer.len() - keep);
+                false
+            }
+        }
+    }
+
+    fn after_delimiter(&mut self) -> Result<bool, ReadError> {
+        /*
+         * Skip transport padding
+         */
+        let padding: usize = self
+            .buffer
+            .iter()
+            .take_while(|b: &&u8| **b == b' ' || **b == b'\t')
+            .count();
+
+        if self.buffer.len() < padding + 2 {
+            return Ok(false);
+        }
+
+        match &self.buffer[padding..padding + 2] {
+            b"--" => self.state = State::End,
+            b"\r\n" => self.state = State::Headers,
+            _ => return Err(ReadError::Status(400)),
+        }
+
+        self.buffer.drain(..padding + 2);
+        Ok(true)
+    }
+
+    async fn headers(&mut self) -> Result<bool, ReadError> {
+        if self.parts.len() >= self.max_parts {
+            return Err(ReadError::Status(413));
+        }
+        /*
+         * Part without Headers starts with the blank line
+         */
+        let end: usize = if self.buffer.starts_with(b"\r\n") {
+            0
+        } else {
+            match self.find(b"\r\n\r\n") {
+                Some(i) => i + 2,
+                None if self.buffer.len() > MAX_PART_HEADER => return Err(ReadError::Status(400)),
+                None => return Ok(false),
+            }
+        };
+
+        let block: String = String::from_utf8_lossy(&self.buffer[..end]).to_string();
+        self.buffer.drain(..end + 2);
+
+        let headers: Headers = parse_headers(block.lines(), self.max_header_count).await?;
+
+        /*
+         * Content-Disposition: form-data; name="field"; filename="file.txt"
+         */
+        let disposition: String = match headers.get("content-disposition") {
+            Some(x) => x.to_owned(),
+            None => return Err(ReadError::Status(400)),
+        };
+
+        let name: Option<String> = disposition_param(&disposition, "name");
+        let filename: Option<String> = disposition_param(&disposition, "filename");
+
+        let name: String = match name {
+            Some(x) => x,
+            None => return Err(ReadError::Status(400)),
+        };
+
+        let mut part: Part = Part {
+            name,
+            filename,
+            content_type: headers.get("content-type").map(|v: &str| v.to_owned()),
+            headers,
+            path: None,
+            size: 0,
+            data: Vec::new(),
+        };
+
+        if part.filename.is_some() {
+            let path: PathBuf = self.dir.join(format!(
+                "oxidy-upload-{}-{}-{}",
+                process::id(),
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos())
+                    .unwrap_or_default(),
+                UPLOAD_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            let mut options: OpenOptions = OpenOptions::new();
+            options.write(true).create_new(true);
+            /*
+             * Upload Directory is usually the shared Temp Directory
+             */
+            #[cfg(unix)]
+            options.mode(0o600);
+
+            let file: Result<File, tokio::io::Error> = options.open(&path).await;
+
+            match file {
+                Ok(f) => self.file = Some(f),
+                Err(e) => {
+                    println!("[Error] Fail to create Upload File:\n{}", e);
+                    return Err(ReadError::Status(500));
+                }
+            }
+
+            part.path = Some(path);
+        }
+
+        self.part = Some(part);
+        self.state = State::Data;
+        Ok(true)
+    }
+
+    async fn data(&mut self) -> Result<bool, ReadError> {
+        let found: Option<usize> = self.find(&self.delimiter.clone());
+        /*
+         * Hold back bytes which may start a split Delimiter
+         */
+        let available: usize = match found {
+            Some(i) => i,
+            None => self.buffer.len().saturating_sub(self.delimiter.len() - 1),
+        };
+
+        if available > 0 {
+            let chunk: Vec<u8> = self.buffer.drain(..available).collect();
+            self.append(&chunk).await?;
+        }
+
+        if found.is_none() {
+            return Ok(false);
+        }
+
+        self.buffer.drain(..self.delimiter.len());
+
+        if let Some(mut f) = self.file.take() {
+            if let Err(e) = f.flush().await {
+                println!("[Error] Fail to Flush Upload File:\n{}", e);
+                return Err(ReadError::Status(500));
+            }
+        }
+
+        if let Some(p) = self.part.take() {
+            self.parts.push(p);
+        }
+
+        self.state = State::Delimiter;
+        Ok(true)
+    }
+
+    async fn append(&mut self, chunk: &[u8]) -> Result<(), ReadError> {
+        let part: &mut Part = match self.part.as_mut() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        part.size += chunk.len();
+
+        match self.file.as_mut() {
+            Some(f) => {
+                if part.size > self.max_file_size {
+                    return Err(ReadError::Status(413));
+                }
+
+                if let Err(e) = f.write_all(chunk).await {
+                    println!("[Error] Fail to Write Upload File:\n{}", e);
+                    return Err(ReadError::Status(500));
+                }
+            }
+            None => {
+                self.fields += chunk.len();
+
+                if self.fields > self.max_field_size {
+                    return Err(ReadError::Status(413));
+                }
+
+                part.data.extend_from_slice(chunk);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/*
+ * Get quoted or plain Parameter from Content-Disposition
+ */
+fn disposition_param(disposition: &str, key: &str) -> Option<String> {
+    /*
+     * Split on ';' outside of quotes
+     */
+    let mut params: Vec<String> = vec![String::new()];
+    let mut quoted: bool = false;
+    let mut escaped: bool = false;
+
+    for ch in disposition.chars() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        params.last_mut()?.push(ch);
+    }
+
+    params.iter().skip(1).find_map(|param: &String| {
+        let (k, v) = param.split_once('=')?;
+
+        if !k.trim().eq_ignore_ascii_case(key) {
+            return None;
+        }
+
+        let v: &str = v.trim();
+
+        match v.strip_prefix('"').and_then(|x: &str| x.strip_suffix('"')) {
+            Some(x) => Some(x.replace("\\\"", "\"").replace("\\\\", "\\")),
+            None => Some(v.to_owned()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+     * multipart/form-data Body with Boundary "x", Parts are (Name, Filename, Data)
+     */
+    fn body(parts: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
+        let mut body: String = String::new();
+
+        for (name, filename, data) in parts {
+            body.push_str("--x\r\nContent-Disposition: form-data; name=\"");
+            body.push_str(name);
+            body.push('"');
+
+            if let Some(f) = filename {
+                body.push_str(&format!("; filename=\"{}\"", f));
+            }
+
+            body.push_str(&format!("\r\n\r\n{}\r\n", data));
+        }
+
+        body.push_str("--x--\r\n");
+        body.into_bytes()
+    }
+
+    #[tokio::test]
+    async fn fields() {
+        let mut parser: MultipartParser = MultipartParser::new("x", &Config::default());
+
+        parser
+            .write(&body(&[("a", None, "1"), ("b", None, "2")]))
+            .await
+            .ok()
+            .unwrap();
+
+        let parts: Vec<Part> = parser.finish().await.ok().unwrap();
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].name, "b");
+        assert_eq!(parts[1].data, b"2");
+    }
+
+    #[tokio::test]
+    async fn part_count_limit() {
+        let config: Config = Config {
+            max_parts: 2,
+            ..Config::default()
+        };
+
+        let mut parser: MultipartParser = MultipartParser::new("x", &config);
+
+        let written = parser
+            .write(&body(&[
+                ("a", None, "1"),
+                ("b", None, "2"),
+                ("c", None, "3"),
+            ]))
+            .await;
+
+        assert!(matches!(written, Err(ReadError::Status(413))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn upload_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut parser: MultipartParser = MultipartParser::new("x", &Config::default());
+
+        parser
+            .write(&body(&[("f", Some("a.txt"), "secret")]))
+            .await
+            .ok()
+            .unwrap();
+
+        let parts: Vec<Part> = parser.finish().await.ok().unwrap();
+        let path: PathBuf = parts[0].path.clone().unwrap();
+
+        let mode: u32 = std::fs::metadata(&path).unwrap().permissions().mode();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::structs::body::Body;
+use crate::structs::context::Context;
+use crate::structs::headers::Headers;
+use crate::structs::request::Request;
+use crate::structs::response::Response;
+use crate::structs::tls::TlsInfo;
+use crate::utils::parse_http_version::parse_http_version;
+use crate::utils::parse_method::parse_method;
+use crate::utils::parse_path::parse_path;
+use tokio::join;
+
+/*
+ * Context of an incoming Request (Body is read separately)
+ */
+pub(crate) async fn new_context(
+    address: String,
+    header: String,
+    headers: Headers,
+    tls: Option<TlsInfo>,
+) -> Context {
+    /*
+     * Incoming
+     */
+    let ((url, path, query), method, http_version) = join!(
+        parse_path(header.clone()),
+        parse_method(header.clone()),
+        parse_http_version(header.clone())
+    );
+
+    Context {
+        next: true,
+        state: Vec::new(),
+        request: Request {
+            address,
+            header,
+            headers,
+            tls,
+            param_store: Vec::new(),
+            query_store: Vec::new(),
+            form_store: Vec::new(),
+            method,
+            url,
+            path,
+            query,
+            http_version,
+            body: Vec::new(),
+            trailers: Headers::new(),
+            parts: Vec::new(),
+        },
+        response: Response {
+            headers: Headers::new(),
+            body: Body::new(),
+            status: 200,
+            content_type: "text/html".to_owned(),
+            upgrade: None,
+        },
+    }
+}
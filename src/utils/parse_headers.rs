@@ -0,0 +1,119 @@
+use crate::structs::definition::ReadError;
+use crate::structs::headers::Headers;
+
+/*
+ * Parse Header Fields (RFC 7230 Section 3.2)
+ * field-name ":" OWS field-value OWS
+ * obs-fold continuation lines are joined with a single space
+ */
+pub(crate) async fn parse_headers<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    max_count: usize,
+) -> Result<Headers, ReadError> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for ln in lines.filter(|ln: &&str| !ln.is_empty()) {
+        /*
+         * obs-fold
+         */
+        if ln.starts_with(' ') || ln.starts_with('\t') {
+            match fields.last_mut() {
+                Some((_, v)) => {
+                    if !v.is_empty() {
+                        v.push(' ');
+                    }
+                    v.push_str(ln.trim());
+                }
+                None => return Err(ReadError::Status(400)),
+            }
+            continue;
+        }
+        /*
+         * No whitespace allowed in or after the Field Name
+         */
+        match ln.split_once(':') {
+            Some((k, v)) if is_token(k) => fields.push((k.to_owned(), v.trim().to_owned())),
+            _ => return Err(ReadError::Status(400)),
+        }
+
+        if fields.len() > max_count {
+            return Err(ReadError::Status(431));
+        }
+    }
+
+    let mut headers: Headers = Headers::new();
+
+    for (k, v) in fields {
+        headers.append(&k, &v);
+    }
+
+    Ok(headers)
+}
+
+/*
+ * RFC 7230 token
+ */
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b: u8| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn parse(header: &str, max_count: usize) -> Result<Headers, ReadError> {
+        parse_headers(header.split("\r\n"), max_count).await
+    }
+
+    #[tokio::test]
+    async fn fields() {
+        let headers: Headers = parse("Host: a\r\nAccept:  text/html \r\nX-Empty:\r\n", 100)
+            .await
+            .ok()
+            .unwrap();
+
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers.get("host"), Some("a"));
+        assert_eq!(headers.get("accept"), Some("text/html"));
+        assert_eq!(headers.get("x-empty"), Some(""));
+    }
+
+    #[tokio::test]
+    async fn obs_fold() {
+        let headers: Headers = parse("X-Long: a\r\n  b\r\n\tc\r\n", 100)
+            .await
+            .ok()
+            .unwrap();
+
+        assert_eq!(headers.get("x-long"), Some("a b c"));
+        /*
+         * Continuation without a Field
+         */
+        assert!(matches!(
+            parse(" b\r\n", 100).await,
+            Err(ReadError::Status(400))
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_name() {
+        for header in ["Host : a", "Ho st: a", ": a", "Host"] {
+            assert!(matches!(
+                parse(header, 100).await,
+                Err(ReadError::Status(400))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn count_limit() {
+        assert!(parse("A: 1\r\nB: 2", 2).await.is_ok());
+        assert!(matches!(
+            parse("A: 1\r\nB: 2\r\nC: 3", 2).await,
+            Err(ReadError::Status(431))
+        ));
+    }
+}
@@ -0,0 +1,12 @@
+/*
+ * Media Type of a Content-Type, lowercase & without Parameters
+ * e.g. "Multipart/Form-Data; boundary=x" -> "multipart/form-data"
+ */
+pub(crate) async fn parse_mime(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
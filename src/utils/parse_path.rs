@@ -6,13 +6,12 @@ pub(crate) async fn parse_path(header: String) -> (String, String, String) {
         .to_owned()
         .split_whitespace()
         .map(|x: &str| x.to_owned())
-        .into_iter()
         .nth(1)
         .unwrap_or_else(|| "/".to_owned());
 
     let url_split: Vec<String> = url.split('?').map(|x: &str| x.to_owned()).collect();
 
-    let path: String = match url_split.get(0) {
+    let path: String = match url_split.first() {
         Some(x) => x.to_owned(),
         None => String::new(),
     };
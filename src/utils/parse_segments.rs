@@ -0,0 +1,15 @@
+use crate::utils::percent_decode::percent_decode;
+
+/*
+ * Split Path into Percent-Decoded Segments
+ * Decoding each Segment keeps an encoded "/" (%2F) inside its Segment
+ */
+pub(crate) async fn parse_segments(path: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+
+    for segment in path.split('/').filter(|x: &&str| !x.is_empty()) {
+        segments.push(percent_decode(segment, false).await);
+    }
+
+    segments
+}
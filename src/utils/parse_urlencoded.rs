@@ -0,0 +1,21 @@
+use crate::utils::percent_decode::percent_decode;
+
+/*
+ * Parse application/x-www-form-urlencoded (Query String & Form Body)
+ * Keeps order & repeated keys
+ */
+pub(crate) async fn parse_urlencoded(input: &str) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+
+    for kv in input.split('&') {
+        let (k, v) = kv.split_once('=').unwrap_or((kv, ""));
+
+        if k.is_empty() {
+            continue;
+        }
+
+        pairs.push((percent_decode(k, true).await, percent_decode(v, true).await));
+    }
+
+    pairs
+}
@@ -0,0 +1,60 @@
+/*
+ * Percent-Decode (%XX) a URL component
+ * Invalid escapes are kept as is, invalid UTF-8 is replaced
+ */
+pub(crate) async fn percent_decode(input: &str, plus_as_space: bool) -> String {
+    let bytes: &[u8] = input.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i: usize = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                /*
+                 * Exactly two Hex Digits (from_str_radix alone takes a Sign)
+                 */
+                let digits: &[u8] = &bytes[i + 1..i + 3];
+
+                let hex: Option<u8> = match digits.iter().all(|b: &u8| b.is_ascii_hexdigit()) {
+                    true => std::str::from_utf8(digits)
+                        .ok()
+                        .and_then(|h: &str| u8::from_str_radix(h, 16).ok()),
+                    false => None,
+                };
+
+                match hex {
+                    Some(b) => {
+                        decoded.push(b);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn decode() {
+        assert_eq!(percent_decode("a%20b%2Fc", false).await, "a b/c");
+        assert_eq!(percent_decode("a+b", true).await, "a b");
+        assert_eq!(percent_decode("a+b", false).await, "a+b");
+    }
+
+    #[tokio::test]
+    async fn invalid_escapes_are_kept() {
+        assert_eq!(percent_decode("%+F", false).await, "%+F");
+        assert_eq!(percent_decode("%-1x", false).await, "%-1x");
+        assert_eq!(percent_decode("%zz", false).await, "%zz");
+    }
+}
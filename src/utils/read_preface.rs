@@ -0,0 +1,35 @@
+use crate::structs::definition::ReadError;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/*
+ * HTTP/2 Connection Preface
+ */
+pub(crate) const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/*
+ * Read until the Buffer is the HTTP/2 Preface or can no longer become it
+ * Everything read stays in the Buffer
+ */
+pub(crate) async fn read_preface<T: AsyncRead + Unpin>(
+    stream: &mut T,
+    buffer: &mut Vec<u8>,
+) -> Result<bool, ReadError> {
+    let mut chunk: [u8; 4096] = [0; 4096];
+
+    loop {
+        let size: usize = buffer.len().min(PREFACE.len());
+
+        if buffer[..size] != PREFACE[..size] {
+            return Ok(false);
+        }
+
+        if size == PREFACE.len() {
+            return Ok(true);
+        }
+
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err(ReadError::Close),
+            Ok(x) => buffer.extend_from_slice(&chunk[..x]),
+        }
+    }
+}
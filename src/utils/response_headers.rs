@@ -0,0 +1,70 @@
+use crate::structs::headers::Headers;
+
+/*
+ * Framing is set from the Body, a second Length would let a Proxy and the
+ * Client split the Response differently (Response Smuggling)
+ */
+const FRAMING_HEADERS: [&str; 2] = ["content-length", "transfer-encoding"];
+
+/*
+ * Response Header Lines (each ending with CRLF)
+ */
+pub(crate) async fn response_headers(headers: &Headers) -> String {
+    let mut response_header: String = String::new();
+
+    headers.iter().for_each(|(k, v)| {
+        if FRAMING_HEADERS
+            .iter()
+            .any(|h: &&str| k.eq_ignore_ascii_case(h))
+        {
+            println!("[Error] Skip Response Header set from the Body: {}", k);
+            return;
+        }
+        /*
+         * Line breaks would inject Headers (Response Splitting)
+         */
+        if k.contains(['\r', '\n']) || v.contains(['\r', '\n']) {
+            println!("[Error] Skip Response Header with line break: {}", k);
+            return;
+        }
+
+        response_header.push_str(&format!("{}: {}\r\n", k, v));
+    });
+
+    response_header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn lines() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Vary", "Accept");
+        headers.append("Vary", "Accept-Encoding");
+
+        assert_eq!(
+            response_headers(&headers).await,
+            "Vary: Accept\r\nVary: Accept-Encoding\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_framing() {
+        let mut headers: Headers = Headers::new();
+        headers.append("Content-Length", "0");
+        headers.append("transfer-encoding", "chunked");
+        headers.append("Server", "oxidy");
+
+        assert_eq!(response_headers(&headers).await, "Server: oxidy\r\n");
+    }
+
+    #[tokio::test]
+    async fn skip_line_break() {
+        let mut headers: Headers = Headers::new();
+        headers.append("X-Split", "a\r\nSet-Cookie: b=1");
+
+        assert_eq!(response_headers(&headers).await, "");
+    }
+}
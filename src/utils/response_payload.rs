@@ -1,40 +1,79 @@
+use crate::structs::body::ChunkStream;
 use crate::structs::context::Context;
+use crate::structs::definition::IoWriter;
+use crate::utils::response_headers::response_headers;
 use crate::utils::status_string::status_string;
+use futures::StreamExt;
 use tokio::io::{AsyncWriteExt, Error};
-use tokio::net::tcp::OwnedWriteHalf;
 
+/*
+ * Write Response, returns if the Connection can serve another Request
+ */
 pub(crate) async fn response_payload(
-    mut writer: OwnedWriteHalf,
+    writer: &mut IoWriter,
     context: Context,
     http_version: f64,
-) {
+    keep_alive: bool,
+) -> bool {
     /*
      * Prepare Response Headers
      */
-    let mut response_header: String = String::new();
-
-    context.response.header.iter().for_each(|(k, v)| {
-        response_header.push_str(&format!("{}: {}\r\n", k, v));
-    });
+    let response_header: String = response_headers(&context.response.headers).await;
     /*
      * Prepare Response Payload
      */
     let status_str: String = status_string(context.response.status).await;
 
-    let response: String = format!(
-        "HTTP/{0} {1} {2}\r\n{3}Content-Type: {4}\r\nContent-Length: {5}\r\n\r\n{6}",
+    let stream: Option<ChunkStream> = context.response.body.take_stream();
+    /*
+     * 1xx, 204 & 304 have no Body (and no Framing), HEAD gets the Framing
+     * of the Body without its bytes
+     */
+    let no_body: bool = matches!(context.response.status, 100..=199 | 204 | 304);
+    let send_body: bool = !no_body && !context.request.method.eq_ignore_ascii_case("HEAD");
+    /*
+     * Streamed Bodies have no known length: chunked on HTTP/1.1,
+     * delimited by closing the connection on HTTP/1.0
+     */
+    let chunked: bool = http_version >= 1.1;
+
+    let framing: String = match stream {
+        _ if no_body => String::new(),
+        Some(_) if chunked => "Transfer-Encoding: chunked\r\n".to_owned(),
+        Some(_) => String::new(),
+        None => format!("Content-Length: {}\r\n", context.response.body.len()),
+    };
+
+    let keep_alive: bool = keep_alive && (chunked || stream.is_none() || !send_body);
+
+    let connection: &str = match (keep_alive, http_version >= 1.1) {
+        (true, true) => "",
+        (true, false) => "Connection: keep-alive\r\n",
+        (false, _) => "Connection: close\r\n",
+    };
+
+    let response_head: String = format!(
+        "HTTP/{0:.1} {1} {2}\r\n{3}Content-Type: {4}\r\n{5}{6}\r\n",
         http_version,
         context.response.status,
         status_str,
         response_header,
         context.response.content_type,
-        context.response.body.len(),
-        context.response.body,
+        framing,
+        connection,
     );
+    /*
+     * Body bytes are written unmodified
+     */
+    let mut response: Vec<u8> = response_head.into_bytes();
+
+    if send_body {
+        response.extend_from_slice(context.response.body.bytes());
+    }
     /*
      * Write Payload
      */
-    let stream_write: Result<(), Error> = writer.write_all(response.as_bytes()).await;
+    let stream_write: Result<(), Error> = writer.write_all(&response).await;
 
     if stream_write.is_err() {
         println!(
@@ -42,7 +81,22 @@ pub(crate) async fn response_payload(
             stream_write.err().unwrap()
         );
 
-        return;
+        return false;
+    }
+    /*
+     * Write Streamed Body
+     */
+    if let Some(stream) = stream.filter(|_| send_body) {
+        let stream_write: Result<(), Error> = write_stream(writer, stream, chunked).await;
+
+        if stream_write.is_err() {
+            println!(
+                "[Error] Fail to Write Body Stream:\n{}",
+                stream_write.err().unwrap()
+            );
+
+            return false;
+        }
     }
     /*
      * Flush Payload
@@ -54,5 +108,44 @@ pub(crate) async fn response_payload(
             "[Error] Fail to Flush Stream:\n{}",
             stream_flush.err().unwrap()
         );
+
+        return false;
     }
+
+    keep_alive
+}
+
+/*
+ * Write Chunks as produced, each write waits for the socket (backpressure)
+ */
+async fn write_stream(
+    writer: &mut IoWriter,
+    mut stream: ChunkStream,
+    chunked: bool,
+) -> Result<(), Error> {
+    while let Some(chunk) = stream.next().await {
+        let chunk: Vec<u8> = chunk?;
+        /*
+         * Empty Chunk would end the Body early
+         */
+        if chunk.is_empty() {
+            continue;
+        }
+
+        if chunked {
+            writer
+                .write_all(format!("{:X}\r\n", chunk.len()).as_bytes())
+                .await?;
+            writer.write_all(&chunk).await?;
+            writer.write_all(b"\r\n").await?;
+        } else {
+            writer.write_all(&chunk).await?;
+        }
+    }
+
+    if chunked {
+        writer.write_all(b"0\r\n\r\n").await?;
+    }
+
+    Ok(())
 }
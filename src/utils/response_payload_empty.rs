@@ -1,7 +1,7 @@
+use crate::structs::definition::IoWriter;
 use tokio::io::{AsyncWriteExt, Error};
-use tokio::net::tcp::OwnedWriteHalf;
 
-pub(crate) async fn response_payload_empty(mut writer: OwnedWriteHalf) {
+pub(crate) async fn response_payload_empty(writer: &mut IoWriter) {
     /*
      * Write Payload
      */
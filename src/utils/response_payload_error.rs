@@ -0,0 +1,41 @@
+use crate::structs::definition::IoWriter;
+use crate::utils::status_string::status_string;
+use tokio::io::{AsyncWriteExt, Error};
+
+/*
+ * Reject Request before it reaches any Route
+ */
+pub(crate) async fn response_payload_error(writer: &mut IoWriter, status: usize) {
+    let status_str: String = status_string(status).await;
+
+    let response: String = format!(
+        "HTTP/1.1 {0} {1}\r\nContent-Type: text/plain\r\nContent-Length: {2}\r\nConnection: close\r\n\r\n{1}",
+        status,
+        status_str,
+        status_str.len(),
+    );
+    /*
+     * Write Payload
+     */
+    let stream_write: Result<(), Error> = writer.write_all(response.as_bytes()).await;
+
+    if stream_write.is_err() {
+        println!(
+            "[Error] Fail to Write Error Stream:\n{}",
+            stream_write.err().unwrap()
+        );
+
+        return;
+    }
+    /*
+     * Flush Payload
+     */
+    let stream_flush: Result<(), Error> = writer.flush().await;
+
+    if stream_flush.is_err() {
+        println!(
+            "[Error] Fail to Flush Error Stream:\n{}",
+            stream_flush.err().unwrap()
+        );
+    }
+}
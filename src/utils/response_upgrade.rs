@@ -0,0 +1,42 @@
+use crate::structs::context::Context;
+use crate::structs::definition::{IoReader, IoStream, IoWriter, OnUpgrade};
+use crate::structs::websocket::WebSocket;
+use crate::utils::response_headers::response_headers;
+use tokio::io::{AsyncWriteExt, Error};
+
+/*
+ * Send 101 Switching Protocols & hand the Stream to the WebSocket Handler
+ * Buffer holds Frames received along with the Request
+ */
+pub(crate) async fn response_upgrade(
+    reader: IoReader,
+    mut writer: IoWriter,
+    buffer: Vec<u8>,
+    context: Context,
+    on_upgrade: OnUpgrade,
+    max_message_size: usize,
+) {
+    let response_header: String = response_headers(&context.response.headers).await;
+
+    let response: String = format!(
+        "HTTP/1.1 101 Switching Protocols\r\n{}\r\n",
+        response_header
+    );
+    /*
+     * Write Payload
+     */
+    let stream_write: Result<(), Error> = writer.write_all(response.as_bytes()).await;
+
+    if stream_write.is_err() {
+        println!(
+            "[Error] Fail to Write Upgrade Stream:\n{}",
+            stream_write.err().unwrap()
+        );
+
+        return;
+    }
+
+    let stream: IoStream = reader.unsplit(writer);
+
+    (on_upgrade.0)(context, WebSocket::new(stream, buffer, max_message_size)).await;
+}
@@ -0,0 +1,188 @@
+use crate::server::Server;
+use crate::structs::definition::IoStream;
+use crate::structs::handoff::Handoff;
+use crate::structs::listener::Listener;
+use crate::structs::shutdown::Shutdown;
+use crate::structs::stats::ActiveGuard;
+use crate::structs::tls::TlsInfo;
+use crate::utils::hand_over::hand_over;
+use crate::utils::handler::handler;
+use crate::utils::tls_info::tls_info;
+use futures::stream::{select_all, unfold, BoxStream, SelectAll};
+use futures::StreamExt;
+use std::future::Future;
+use std::process::{Child, Command};
+use std::sync::Arc;
+use tokio::io::Error;
+use tokio::select;
+use tokio::sync::mpsc::Receiver;
+use tokio::task::{spawn_blocking, JoinError, JoinSet};
+use tokio::time::timeout;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+/*
+ * Accepted Connection & the TLS Acceptor of its Listener
+ */
+type Accepted = (Result<(IoStream, String), Error>, Option<TlsAcceptor>);
+
+/*
+ * Accept Connections on all Listeners until the Signal completes or they are
+ * handed over to a new Process, then drain them
+ */
+pub(crate) async fn serve<F>(
+    server: Server,
+    listeners: Vec<(Listener, Option<TlsAcceptor>)>,
+    signal: F,
+    mut handoffs: Receiver<Handoff>,
+) where
+    F: Future<Output = ()>,
+{
+    let (sender, shutdown) = Shutdown::new();
+    /*
+     * Shared with the Accept Streams, to be handed over
+     */
+    let listeners: Vec<(Arc<Listener>, Option<TlsAcceptor>)> = listeners
+        .into_iter()
+        .map(|(listener, acceptor)| (Arc::new(listener), acceptor))
+        .collect();
+
+    let sockets: Vec<Arc<Listener>> = listeners
+        .iter()
+        .map(|(listener, _)| listener.clone())
+        .collect();
+    /*
+     * One Stream of Connections per Listener, merged
+     */
+    let mut accepts: SelectAll<BoxStream<'static, Accepted>> = select_all(
+        listeners
+            .into_iter()
+            .map(|listener: (Arc<Listener>, Option<TlsAcceptor>)| {
+                unfold(listener, |(listener, acceptor)| async move {
+                    let listener_accept: Result<(IoStream, String), Error> =
+                        listener.accept().await;
+
+                    Some(((listener_accept, acceptor.clone()), (listener, acceptor)))
+                })
+                .boxed()
+            }),
+    );
+    /*
+     * Running Connections
+     */
+    let mut connections: JoinSet<()> = JoinSet::new();
+
+    tokio::pin!(signal);
+    /*
+     * Connection Loop
+     */
+    loop {
+        select! {
+            _ = &mut signal => break,
+            Some(handoff) = handoffs.recv() => {
+                /*
+                 * fork & exec block, keep them off the Runtime Threads
+                 */
+                let handed_over: Vec<Arc<Listener>> = sockets.clone();
+                let command: Command = handoff.command;
+
+                let child_spawn: Result<Child, Error> =
+                    spawn_blocking(move || hand_over(&handed_over, command))
+                        .await
+                        .unwrap_or_else(|e: JoinError| Err(Error::other(e)));
+                /*
+                 * The new Process accepts from now on, this one only drains
+                 */
+                let handed: bool = child_spawn.is_ok();
+
+                let _ = handoff.reply.send(child_spawn);
+
+                if handed {
+                    for socket in &sockets {
+                        socket.keep_file();
+                    }
+                    break;
+                }
+            }
+            Some((listener_accept, acceptor)) = accepts.next() => {
+                if listener_accept.is_err() {
+                    continue;
+                }
+
+                let (stream, address) = listener_accept.expect("[Error] Fail to Accept Connection");
+
+                let active: ActiveGuard = server.stats.connection();
+
+                let connection_serve = connection(
+                    server.clone(),
+                    address,
+                    stream,
+                    acceptor,
+                    shutdown.clone(),
+                );
+
+                connections.spawn(async move {
+                    connection_serve.await;
+                    drop(active);
+                });
+            }
+            /*
+             * Forget finished Connections
+             */
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+        }
+    }
+    /*
+     * Stop accepting, close idle Connections & let running Requests finish
+     */
+    drop(accepts);
+    drop(sockets);
+    let _ = sender.send(true);
+
+    let drain = async { while connections.join_next().await.is_some() {} };
+
+    if timeout(server.config.shutdown_timeout, drain)
+        .await
+        .is_err()
+    {
+        println!(
+            "[Error] Shutdown Timeout, aborting {} Connections",
+            connections.len()
+        );
+
+        connections.shutdown().await;
+    }
+}
+
+/*
+ * TLS Handshake (if any), then serve the Connection
+ */
+async fn connection(
+    server: Server,
+    address: String,
+    stream: IoStream,
+    acceptor: Option<TlsAcceptor>,
+    shutdown: Shutdown,
+) {
+    let acceptor: TlsAcceptor = match acceptor {
+        Some(x) => x,
+        None => {
+            handler(server, address, stream, None, shutdown).await;
+            return;
+        }
+    };
+
+    let stream: TlsStream<IoStream> =
+        match timeout(server.config.idle_timeout, acceptor.accept(stream)).await {
+            Ok(Ok(x)) => x,
+            Ok(Err(e)) => {
+                println!("[Error] Fail to Handshake TLS:\n{}", e);
+                return;
+            }
+            Err(_) => return,
+        };
+
+    let info: TlsInfo = tls_info(stream.get_ref().1);
+
+    handler(server, address, Box::new(stream), Some(info), shutdown).await;
+}
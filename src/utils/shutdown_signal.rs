@@ -0,0 +1,35 @@
+#[cfg(unix)]
+use tokio::select;
+use tokio::signal::ctrl_c;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+/// Wait for SIGTERM or SIGINT (Ctrl+C)
+///
+/// Shutdown signal for `Server::run_with_shutdown`.
+///
+/// # Example
+///
+/// ```
+/// use oxidy::{Server, shutdown_signal};
+///
+/// let app = Server::new();
+/// /* app.run_with_shutdown("127.0.0.1:3000", shutdown_signal()).await; */
+/// ```
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut terminate: Signal =
+            signal(SignalKind::terminate()).expect("[Error] Fail to listen for SIGTERM");
+
+        select! {
+            _ = terminate.recv() => {}
+            _ = ctrl_c() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = ctrl_c().await;
+    }
+}
@@ -0,0 +1,16 @@
+use std::fs::{File, Metadata};
+use std::os::fd::BorrowedFd;
+use std::os::unix::fs::MetadataExt;
+use tokio::io::Error;
+
+/*
+ * Identity of an open Socket (Device & Inode), the same in every Process
+ * holding it, e.g. "8:1234567"
+ */
+pub(crate) fn socket_id(fd: BorrowedFd) -> Result<String, Error> {
+    let file: File = File::from(fd.try_clone_to_owned()?);
+
+    let metadata: Metadata = file.metadata()?;
+
+    Ok(format!("{}:{}", metadata.dev(), metadata.ino()))
+}
@@ -6,10 +6,16 @@
  */
 pub(crate) async fn status_string(code: usize) -> String {
     match code {
+        501 => "Not Implemented".to_owned(),
+        500 => "Internal Server Error".to_owned(),
+
+        431 => "Request Header Fields Too Large".to_owned(),
         426 => "Upgrade Required".to_owned(),
+        415 => "Unsupported Media Type".to_owned(),
         414 => "URI Too Long".to_owned(),
         413 => "Payload Too Large".to_owned(),
         410 => "Gone".to_owned(),
+        408 => "Request Timeout".to_owned(),
         405 => "Method Not Allowed".to_owned(),
         404 => "Not Found".to_owned(),
         403 => "Forbidden".to_owned(),
@@ -24,6 +30,7 @@ pub(crate) async fn status_string(code: usize) -> String {
         302 => "Found".to_owned(),
         301 => "Moved Permanently".to_owned(),
 
+        204 => "No Content".to_owned(),
         202 => "Accepted".to_owned(),
         201 => "Created".to_owned(),
         200 => "OK".to_owned(),
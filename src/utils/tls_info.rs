@@ -0,0 +1,79 @@
+use crate::structs::tls::{PeerCertificate, TlsInfo};
+use ring::digest::{digest, SHA256};
+use rustls::pki_types::CertificateDer;
+use rustls::ServerConnection;
+use std::net::IpAddr;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::GeneralName;
+
+/*
+ * TLS Session of an accepted Connection
+ */
+pub(crate) fn tls_info(connection: &ServerConnection) -> TlsInfo {
+    let peer_certificates: Vec<PeerCertificate> = connection
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .map(|x: &CertificateDer| peer_certificate(x.as_ref()))
+        .collect();
+
+    TlsInfo {
+        protocol: connection
+            .alpn_protocol()
+            .map(|x: &[u8]| String::from_utf8_lossy(x).to_string()),
+        server_name: connection.server_name().map(|x: &str| x.to_owned()),
+        peer_certificates,
+    }
+}
+
+/*
+ * Subject, Issuer & SANs of a DER Certificate sent by the Client
+ */
+fn peer_certificate(der: &[u8]) -> PeerCertificate {
+    let fingerprint: String = digest(&SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|x: &u8| format!("{:02x}", x))
+        .collect();
+
+    let mut peer: PeerCertificate = PeerCertificate {
+        fingerprint,
+        der: der.to_vec(),
+        ..Default::default()
+    };
+
+    let certificate: X509Certificate = match x509_parser::parse_x509_certificate(der) {
+        Ok((_, x)) => x,
+        Err(e) => {
+            println!("[Error] Fail to Parse Peer Certificate:\n{}", e);
+            return peer;
+        }
+    };
+
+    peer.subject = certificate.subject().to_string();
+    peer.issuer = certificate.issuer().to_string();
+
+    if let Ok(Some(extension)) = certificate.subject_alternative_name() {
+        for name in &extension.value.general_names {
+            let san: Option<String> = match name {
+                GeneralName::DNSName(x) | GeneralName::RFC822Name(x) | GeneralName::URI(x) => {
+                    Some(x.to_string())
+                }
+                GeneralName::IPAddress(x) => match x.len() {
+                    4 => {
+                        Some(IpAddr::from(<[u8; 4]>::try_from(*x).unwrap_or_default()).to_string())
+                    }
+                    16 => {
+                        Some(IpAddr::from(<[u8; 16]>::try_from(*x).unwrap_or_default()).to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            peer.sans.extend(san);
+        }
+    }
+
+    peer
+}
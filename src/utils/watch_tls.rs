@@ -0,0 +1,44 @@
+use crate::structs::tls::TlsConfig;
+use std::time::SystemTime;
+use tokio::fs::metadata;
+use tokio::time::{sleep, Duration};
+
+/*
+ * Reload the Certificate when its PEM Files change
+ * A failed Reload keeps the current Certificate and is retried
+ * The Client CA Bundle is not watched, it is part of the fixed ServerConfig
+ */
+pub(crate) async fn watch_tls(tls: TlsConfig, interval: Duration) {
+    let mut modified: (Option<SystemTime>, Option<SystemTime>) = modified_at(&tls).await;
+
+    loop {
+        sleep(interval).await;
+
+        let current: (Option<SystemTime>, Option<SystemTime>) = modified_at(&tls).await;
+
+        if current == modified {
+            continue;
+        }
+
+        match tls.reload().await {
+            Ok(_) => modified = current,
+            Err(e) => println!("[Error] Fail to reload TLS Certificate:\n{}", e),
+        }
+    }
+}
+
+/*
+ * Modification Time of Certificate & Key File
+ */
+async fn modified_at(tls: &TlsConfig) -> (Option<SystemTime>, Option<SystemTime>) {
+    let cert: Option<SystemTime> = metadata(&tls.cert_path)
+        .await
+        .and_then(|x| x.modified())
+        .ok();
+    let key: Option<SystemTime> = metadata(&tls.key_path)
+        .await
+        .and_then(|x| x.modified())
+        .ok();
+
+    (cert, key)
+}